@@ -0,0 +1,53 @@
+use hell_orm::prelude::*;
+
+
+#[derive(Model)]
+#[table_name = "comments"]
+pub struct Comment {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    body: String,
+}
+
+#[derive(Schema)]
+#[models(Comment)]
+pub struct Schema;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+
+    runtime.block_on(async {
+        let db: AsyncDatabase<Schema> = AsyncDatabase::open("local.db", DatabaseConfig::new().wal()).await?;
+
+        db.insert::<Comment, _, _>(|comment| comment.body(String::from("sent from a task")).finish()).await?;
+
+        let comments = db.query::<Comment, _, _>(|query| query.count()).await?;
+
+        let changed = db.update::<Comment, _>(|query| {
+            query.body(Where::Equal(String::from("sent from a task")))
+                .update()
+                .body(String::from("edited from a task"))
+                .finish()
+        }).await?;
+
+        let removed = db.transaction(|db| {
+            let removed = db.query::<Comment>().body(Where::Equal(String::from("edited from a task"))).delete()?;
+
+            db.insert::<Comment>().body(String::from("replaced in a transaction")).finish()?;
+
+            Ok(removed)
+        }).await?;
+
+        for comment in db.query::<Comment, _, _>(|query| query.all()).await? {
+            println!("comment {}: {}", comment.id, comment.body);
+        }
+
+        println!("{} comments seen from async, {} edited, {} replaced", comments, changed, removed);
+
+        Ok::<_, hell_orm::error::Error>(())
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use hell_orm::prelude::*;
+
+use std::time::Duration;
+
+
+#[derive(Model)]
+#[table_name = "comments"]
+pub struct Comment {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    body: String,
+}
+
+#[derive(Schema)]
+#[models(Comment)]
+struct Schema;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let pool: Pool<Schema> = Pool::open("pool.db", 4, DatabaseConfig::new().wal().busy_timeout(Duration::from_secs(5)))?;
+
+    pool.writer()?.insert::<Comment>().body(String::from("from the pool")).finish()?;
+
+    let counts = std::thread::scope(|scope| {
+        let workers = (0..4)
+            .map(|_| scope.spawn(|| pool.reader()?.query::<Comment>().count()))
+            .collect::<Vec<_>>();
+
+        workers.into_iter()
+            .map(|worker| worker.join().expect("reader thread panicked"))
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+
+    println!("readers saw {:?} comments", counts);
+
+    for comment in pool.reader()?.query::<Comment>().all()? {
+        println!("comment {}: {}", comment.id, comment.body);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use hell_orm::prelude::*;
+
+
+#[derive(Model)]
+#[table_name = "users"]
+pub struct User {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    #[unique]
+    name: String,
+
+    #[version]
+    version: u32,
+}
+
+#[derive(Model)]
+#[table_name = "posts"]
+pub struct Post {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    user: usize,
+    content: String,
+}
+
+#[derive(Projection)]
+#[projection(Post)]
+pub struct PostSummary {
+    id: usize,
+    user: usize,
+}
+
+#[derive(FromRow)]
+pub struct UserActivity {
+    #[row(flatten)]
+    user: User,
+
+    #[row(index = 3)]
+    posts: i64,
+
+    #[row(index = 4)]
+    last_post: Option<String>,
+}
+
+#[derive(Schema)]
+#[models(User, Post)]
+struct Schema;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut db: Database<Schema> = Database::open("queries.db", DatabaseConfig::new().statement_cache_capacity(32))?;
+
+    let user = match db.query::<User>().filter(User::NAME_COL.eq("admin")).get()? {
+        Some(user) => user,
+        None => {
+            db.insert::<User>().name(String::from("admin")).finish()?;
+
+            db.query::<User>().filter(User::NAME_COL.eq("admin")).get()?.ok_or("admin was not inserted")?
+        },
+    };
+
+    for content in ["first", "second", "third"] {
+        db.insert::<Post>().user(user.id).content(String::from(content)).finish()?;
+    }
+
+    // fails with `Error::StaleVersion` if someone else updated the user since it was read
+    db.query::<User>()
+        .id(Where::Equal(user.id))
+        .update()
+        .name(user.name.clone())
+        .expect_version(user.version)
+        .finish()?;
+
+    let matching = db.query::<Post>()
+        .filter(Post::CONTENT_COL.like("s%").or(Post::ID_COL.le(3)).and(Post::USER_COL.in_([user.id])))
+        .order_by(Post::ID_COL.desc())
+        .limit(10)
+        .all()?;
+
+    for post in matching {
+        println!("post {} by user {}: {}", post.id, post.user, post.content);
+    }
+
+    let mut cursor = None;
+
+    loop {
+        let page = db.query::<Post>()
+            .order_by(Post::ID_COL.asc())
+            .paginate_after(cursor.as_ref(), 2)?;
+
+        println!("posts {:?}", page.items.iter().map(|post| post.id).collect::<Vec<usize>>());
+
+        match page.next {
+            Some(next) => cursor = Some(next.to_string().parse()?),
+            None => break,
+        }
+    }
+
+    for (author, posts, first) in db.query::<Post>().group_by(Post::USER_COL).having(count().ge(1)).select((Post::USER_COL, count(), Post::ID_COL.min()))? {
+        println!("user {} wrote {} posts, the first being {:?}", author, posts, first);
+    }
+
+    let authors = db.query::<Post>().filter(Post::CONTENT_COL.ne("")).subquery(Post::USER_COL);
+    let writers = db.query::<User>().filter(User::ID_COL.in_(authors)).count()?;
+
+    println!("{} users wrote posts, {:?} summed post ids", writers, db.query::<Post>().sum(Post::ID_COL)?);
+
+    for summary in db.query::<Post>().select_as::<PostSummary>()? {
+        println!("post {} belongs to user {}", summary.id, summary.user);
+    }
+
+    let counts = query_as!(Schema, "SELECT users.name, COUNT(*) FROM posts INNER JOIN users ON users.id = posts.user WHERE posts.content LIKE ? GROUP BY users.id", "%")
+        .all(&mut db)?;
+
+    for (name, posts) in counts {
+        println!("{} has written {} posts", name, posts);
+    }
+
+    let activity = db.query_raw::<UserActivity>("SELECT users.id, users.name, users.version, COUNT(posts.id), MAX(posts.content) FROM users LEFT JOIN posts ON posts.user = users.id GROUP BY users.id ORDER BY users.id", [])?;
+
+    for entry in activity {
+        println!("{} (version {}) wrote {} posts, last {:?}", entry.user.name, entry.user.version, entry.posts, entry.last_post);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use hell_orm::prelude::*;
+
+
+#[derive(Model)]
+#[table_name = "users"]
+pub struct User {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    #[unique]
+    name: String,
+}
+
+#[derive(Model)]
+#[table_name = "posts"]
+#[timestamps]
+#[soft_delete]
+#[belongs_to(User, key = "user")]
+#[many_to_many(Tag, through = PostTag)]
+pub struct Post {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    user: usize,
+    content: String,
+
+    created_at: i64,
+    updated_at: i64,
+}
+
+#[derive(Model)]
+#[table_name = "tags"]
+pub struct Tag {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    #[unique]
+    label: String,
+}
+
+#[derive(Model)]
+#[table_name = "post_tags"]
+#[belongs_to(Post, key = "post")]
+#[belongs_to(Tag, key = "tag")]
+pub struct PostTag {
+    post: usize,
+    tag: usize,
+}
+
+#[derive(Model)]
+#[table_name = "comments"]
+pub struct Comment {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    parent: Option<usize>,
+    body: String,
+}
+
+#[derive(Schema)]
+#[models(User, Post, Tag, PostTag, Comment)]
+struct Schema;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut db: Database<Schema> = Database::open("relations.db", DatabaseConfig::new().foreign_keys(true))?;
+
+    let user = match db.query::<User>().filter(User::NAME_COL.eq("admin")).get()? {
+        Some(user) => user,
+        None => {
+            db.insert::<User>().name(String::from("admin")).finish()?;
+
+            db.query::<User>().filter(User::NAME_COL.eq("admin")).get()?.ok_or("admin was not inserted")?
+        },
+    };
+
+    let tag = match db.query::<Tag>().filter(Tag::LABEL_COL.eq("news")).get()? {
+        Some(tag) => tag,
+        None => {
+            db.insert::<Tag>().label(String::from("news")).finish()?;
+
+            db.query::<Tag>().filter(Tag::LABEL_COL.eq("news")).get()?.ok_or("tag was not inserted")?
+        },
+    };
+
+    db.insert::<Post>()
+        .user(user.id)
+        .content(String::from("hello"))
+        .finish()?;
+
+    for post in user.posts(&mut db)? {
+        let author = post.user(&mut db)?.map(|user| user.name).unwrap_or_default();
+
+        post.add_tag(&mut db, &tag)?;
+
+        let tags = post.tags(&mut db)?
+            .into_iter()
+            .map(|tag| tag.label)
+            .collect::<Vec<String>>();
+
+        println!("post {} by {}: {} {:?} (created {}, updated {})", post.id, author, post.content, tags, post.created_at, post.updated_at);
+
+        post.remove_tag(&mut db, &tag)?;
+    }
+
+    for (post, author) in db.query::<Post>().join::<User>(on!(Post::user == User::id)).all()? {
+        println!("post {} was written by {}", post.id, author.name);
+    }
+
+    for (user, posts) in db.query::<User>().include::<Post>().all()? {
+        println!("{} has {} posts", user.name, posts.len());
+    }
+
+    db.insert::<Comment>()
+        .body(String::from("first!"))
+        .finish()?;
+
+    let root = db.query::<Comment>().last()?.ok_or("comment was not inserted")?;
+
+    db.insert::<Comment>()
+        .parent(Some(root.id))
+        .body(String::from("welcome"))
+        .finish()?;
+
+    let thread = db.query::<Comment>()
+        .filter(Comment::ID_COL.eq(root.id))
+        .recursive(on!(Comment::parent == Comment::id))
+        .all_with_depth()?;
+
+    for (comment, depth) in thread {
+        println!("{}{} (reply to {:?})", "  ".repeat(depth), comment.body, comment.parent);
+    }
+
+    // soft deleted posts are only hidden from queries
+    db.query::<Post>().filter(Post::USER_COL.eq(user.id)).delete()?;
+
+    println!("{} posts, {} deleted", db.query::<Post>().count()?, db.query::<Post>().only_deleted().count()?);
+
+    Ok(())
+}
@@ -28,14 +28,39 @@ pub struct Post {
 struct Schema;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut db: Database<Schema> = Database::open("local.db")?;
+    let mut db: Database<Schema> = Database::open("local.db", DatabaseConfig::new())?;
+
+    let user = match db.query::<User>().name(Where::Equal(String::from("admin"))).get()? {
+        Some(user) => user,
+        None => {
+            db.insert::<User>()
+                .name(String::from("admin"))
+                .finish()?;
+
+            db.query::<User>().name(Where::Equal(String::from("admin"))).get()?.ok_or("admin was not inserted")?
+        },
+    };
 
     db.insert::<Post>()
-        .user(1)
+        .user(user.id)
         .content(String::new())
         .finish()?;
 
-    Ok(())
-}
+    db.query::<Post>()
+        .user(Where::Equal(user.id))
+        .update()
+        .content(String::from("edited"))
+        .finish()?;
 
+    for post in db.query::<Post>().filter(Post::USER_COL.eq(user.id)).all()? {
+        println!("post {} by user {}: {}", post.id, post.user, post.content);
+    }
 
+    db.query::<Post>()
+        .user(Where::Equal(user.id))
+        .delete()?;
+
+    println!("{} has {} posts left", user.name, db.query::<Post>().count()?);
+
+    Ok(())
+}
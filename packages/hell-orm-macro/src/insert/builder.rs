@@ -1,6 +1,7 @@
 use super::typestate::TypestateCheck;
 use super::field::ModelField;
 
+use syn::ext::IdentExt;
 use syn::punctuated::Punctuated;
 use syn::{Token, Ident, Field, Type};
 use quote::{quote, format_ident, ToTokens};
@@ -64,7 +65,7 @@ pub struct BuilderStructFunctions<'a> {
 impl<'a> BuilderStructFunctions<'a> {
     pub fn new(fields: &'a Punctuated<Field, Token![,]>, builder_ident: &'a Ident, model_ident: &'a Ident) -> BuilderStructFunctions<'a> {
         BuilderStructFunctions {
-            fields: fields.iter().map(|field| ModelField::new(field)).collect(),
+            fields: fields.iter().map(ModelField::new).collect(),
             builder_ident,
             model_ident,
         }
@@ -122,7 +123,9 @@ impl<'a> ToTokens for BuilderStructUpdateFields<'a> {
         for field in self.fields.iter() {
             let ident = field.ident();
 
-            if field.ident() == self.update {
+            if field.ident() == self.update && field.is_nullable() {
+                tokens.extend(quote! { #ident: #ident, });
+            } else if field.ident() == self.update {
                 tokens.extend(quote! { #ident: Some(#ident), });
             } else {
                 tokens.extend(quote! { #ident: self.#ident, });
@@ -151,8 +154,8 @@ impl<'a> ToTokens for BuilderStructFinish<'a> {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let builder_ident = &self.builder_ident;
 
-        let typestate_check = TypestateCheck::new(&self.fields, &self.model);
-        let finish_params = BuilderFinishParams::new(&self.fields);
+        let typestate_check = TypestateCheck::new(self.fields, self.model);
+        let finish_params = BuilderFinishParams::new(self.fields);
 
         tokens.extend(quote! {
             impl<'a> #builder_ident<'a, #typestate_check> {
@@ -175,7 +178,7 @@ pub struct BuilderFinishParams<'a> {
 impl<'a> BuilderFinishParams<'a> {
     pub fn new(fields: &'a Punctuated<Field, Token![,]>) -> BuilderFinishParams<'a> {
         BuilderFinishParams {
-            fields: fields.iter().map(|field| ModelField::new(field)).collect(),
+            fields: fields.iter().map(ModelField::new).collect(),
         }
     }
 }
@@ -184,14 +187,33 @@ impl<'a> ToTokens for BuilderFinishParams<'a> {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         for field in self.fields.iter() {
             let ident = field.ident();
-            let column = field.ident().as_ref().map(|ident| ident.to_string());
+            let type_ = field.type_();
+            let column = field.ident().as_ref().map(|ident| ident.unraw().to_string());
+
+            if field.is_timestamp() {
+                let value = format_ident!("__{}", ident.as_ref().expect("expected a named field"));
 
-            tokens.extend(quote! {
-                if let Some(value) = &self.#ident {
+                tokens.extend(quote! {
+                    let #value = self.#ident.unwrap_or_else(<#type_ as ::hell_orm::schema::timestamp::Timestamp>::now);
                     columns.push(#column);
-                    params.push(value as &dyn ::hell_orm::__macro_export::rusqlite::ToSql);
-                }
-            });
+                    params.push(&#value as &dyn ::hell_orm::__macro_export::rusqlite::ToSql);
+                });
+            } else if field.has_attribute("version") {
+                let value = format_ident!("__{}", ident.as_ref().expect("expected a named field"));
+
+                tokens.extend(quote! {
+                    let #value = self.#ident.unwrap_or_default();
+                    columns.push(#column);
+                    params.push(&#value as &dyn ::hell_orm::__macro_export::rusqlite::ToSql);
+                });
+            } else {
+                tokens.extend(quote! {
+                    if let Some(value) = &self.#ident {
+                        columns.push(#column);
+                        params.push(value as &dyn ::hell_orm::__macro_export::rusqlite::ToSql);
+                    }
+                });
+            }
         }
     }
 }
@@ -13,11 +13,20 @@ impl<'a> ModelField<'a> {
     }
 
     pub fn is_optional(&self) -> bool {
-        if let Type::Path(path) = &self.field.ty && path.path.segments.last().map(|last| last.ident == "Option").unwrap_or(false) {
-            true
-        } else {
-            self.field.attrs.iter().any(|attr| attr.path().is_ident("auto_increment"))
-        }
+        self.is_nullable() || self.has_attribute("auto_increment") || self.has_attribute("version") || self.is_timestamp()
+    }
+
+    /// Whether the field is an `Option`, which the insert builder stores as is.
+    pub fn is_nullable(&self) -> bool {
+        matches!(&self.field.ty, Type::Path(path) if path.path.segments.last().map(|last| last.ident == "Option").unwrap_or(false))
+    }
+
+    pub fn is_timestamp(&self) -> bool {
+        self.has_attribute("created_at") || self.has_attribute("updated_at")
+    }
+
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.field.attrs.iter().any(|attr| attr.path().is_ident(name))
     }
 
     pub fn ident(&self) -> &'a Option<Ident> { &self.field.ident }
@@ -25,4 +34,3 @@ impl<'a> ModelField<'a> {
     pub fn type_(&self) -> &'a Type { &self.field.ty }
 }
 
-
@@ -1,6 +1,6 @@
 mod typestate;
-mod builder;
-mod field;
+pub mod builder;
+pub mod field;
 
 use typestate::TypestateStructs;
 use builder::{BuilderStructInit, BuilderStructFields, BuilderStructFunctions, BuilderStructFinish};
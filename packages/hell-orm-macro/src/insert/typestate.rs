@@ -13,7 +13,7 @@ pub struct TypestateStructs<'a> {
 impl<'a> TypestateStructs<'a> {
     pub fn new(fields: &'a Punctuated<Field, Token![,]>, model: &'a Ident) -> TypestateStructs<'a> {
         TypestateStructs {
-            fields: fields.iter().map(|field| ModelField::new(field)).collect(),
+            fields: fields.iter().map(ModelField::new).collect(),
             model,
         }
     }
@@ -39,7 +39,7 @@ pub struct TypestateCheck<'a> {
 impl<'a> TypestateCheck<'a> {
     pub fn new(fields: &'a Punctuated<Field, Token![,]>, model: &'a Ident) -> TypestateCheck<'a> {
         TypestateCheck {
-            fields: fields.iter().map(|field| ModelField::new(field)).collect(),
+            fields: fields.iter().map(ModelField::new).collect(),
             model,
         }
     }
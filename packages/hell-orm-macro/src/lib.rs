@@ -1,15 +1,26 @@
 mod insert;
 mod query;
+mod update;
+mod relation;
 mod model;
+mod projection;
+mod sql;
+mod row;
 
 use insert::Insert;
 use query::Query;
-use model::Model;
+use update::Update;
+use relation::Relations;
+use model::{Model, PrimaryKey, Version, Columns};
+use projection::Projection;
+use sql::QueryAs;
+use row::FromRow;
 
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::punctuated::Punctuated;
-use syn::{parse_macro_input, Token, DeriveInput, Data, Fields, Type, Meta, Expr, Lit, Attribute};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, parse_quote, Token, DeriveInput, Data, Fields, Type, Meta, Expr, Lit, Attribute, BinOp, ExprBinary, ExprPath};
 
 
 #[inline]
@@ -25,32 +36,82 @@ fn table_name(attrs: &[Attribute]) -> Option<String> {
         .next()
 }
 
-#[proc_macro_derive(Model, attributes(table_name, primary_key, unique, auto_increment))]
+/// Expands the struct level `#[timestamps]` into `#[created_at]` and `#[updated_at]` on the matching fields.
+#[inline]
+fn timestamps(input: &mut DeriveInput) -> Result<(), syn::Error> {
+    if !input.attrs.iter().any(|attribute| attribute.path().is_ident("timestamps")) {
+        return Ok(());
+    }
+
+    let ident = &input.ident;
+
+    if let Data::Struct(data) = &mut input.data && let Fields::Named(fields) = &mut data.fields {
+        for name in ["created_at", "updated_at"] {
+            let Some(field) = fields.named.iter_mut().find(|field| field.ident.as_ref().is_some_and(|ident| ident == name)) else {
+                return Err(syn::Error::new(ident.span(), format!("#[timestamps] requires a `{}` field", name)));
+            };
+
+            if !field.attrs.iter().any(|attribute| attribute.path().is_ident(name)) {
+                let attribute = syn::Ident::new(name, field.span());
+
+                field.attrs.push(parse_quote! { #[#attribute] });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[proc_macro_derive(Model, attributes(table_name, primary_key, unique, auto_increment, timestamps, created_at, updated_at, soft_delete, version, belongs_to, many_to_many))]
 pub fn derive_model(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
+    let mut input = parse_macro_input!(input as DeriveInput);
 
     let Some(table_name) = table_name(&input.attrs) else {
         return TokenStream::from(syn::Error::new(input.ident.span(), "You must provide a table_name attribute").to_compile_error());
     };
 
-    if let Data::Struct(data) = &input.data {
-        if let Fields::Named(fields) = &data.fields {
-            let ident = &input.ident;
+    if let Err(err) = timestamps(&mut input) {
+        return TokenStream::from(err.to_compile_error());
+    }
 
-            let insert = Insert::new(&input, &fields, &table_name);
-            let query = Query::new(&input, &fields);
-            let model = Model::new(&fields.named, &input.attrs, &table_name);
+    if let Data::Struct(data) = &input.data && let Fields::Named(fields) = &data.fields {
+        let ident = &input.ident;
 
-            return TokenStream::from(quote! {
-                #insert
+        let relations = match Relations::parse(&input, fields, &table_name) {
+            Ok(relations) => relations,
+            Err(err) => return TokenStream::from(err.to_compile_error()),
+        };
 
-                #query
+        let insert = Insert::new(&input, fields, &table_name);
+        let query = Query::new(&input, fields, &table_name);
+        let update = Update::new(&input, fields);
+        let model = Model::new(&fields.named, &input.attrs, &table_name, &relations);
+        let primary_key = PrimaryKey::new(&fields.named, ident);
+        let version = Version::new(&fields.named, ident);
+        let columns = Columns::new(&fields.named, ident);
+        let from_row = FromRow::indexed(ident, fields);
 
-                impl ::hell_orm::schema::Model for #ident {
-                    #model
-                }
-            });
-        }
+        return TokenStream::from(quote! {
+            #insert
+
+            #query
+
+            #update
+
+            #relations
+
+            impl ::hell_orm::schema::Model for #ident {
+                #model
+            }
+
+            #from_row
+
+            #primary_key
+
+            #version
+
+            #columns
+        });
     }
 
     TokenStream::from(syn::Error::new(input.ident.span(), "Model can only be derived for structs with named fields").to_compile_error())
@@ -61,35 +122,119 @@ pub fn derive_schema(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let ident = &input.ident;
 
-    if let Data::Struct(data) = input.data {
-        if data.fields.is_empty() {
-            let Some(attribute) = input.attrs.iter().find(|attribute| attribute.path().is_ident("models")) else {
-                return TokenStream::from(syn::Error::new(input.ident.span(), "Schema derive requires #[models(...)] attribute").to_compile_error());
-            };
+    if let Data::Struct(data) = input.data && data.fields.is_empty() {
+        let Some(attribute) = input.attrs.iter().find(|attribute| attribute.path().is_ident("models")) else {
+            return TokenStream::from(syn::Error::new(input.ident.span(), "Schema derive requires #[models(...)] attribute").to_compile_error());
+        };
 
-            let Ok(models): Result<Punctuated<Type, Token![,]>, _> = attribute.parse_args_with(Punctuated::parse_terminated) else {
-                return TokenStream::from(syn::Error::new(input.ident.span(), "Failed to parse models from attribute").to_compile_error());
-            };
+        let Ok(models): Result<Punctuated<Type, Token![,]>, _> = attribute.parse_args_with(Punctuated::parse_terminated) else {
+            return TokenStream::from(syn::Error::new(input.ident.span(), "Failed to parse models from attribute").to_compile_error());
+        };
+
+        let schema_has = models.iter().map(|model| quote! {
+            impl ::hell_orm::schema::SchemaHas<#model> for #ident {}
+        });
+
+        let tables = models.iter().map(|model| quote! {
+            impl ::hell_orm::schema::table::Table<{ ::hell_orm::schema::table::table_hash(<#model as ::hell_orm::schema::Model>::NAME) }> for #ident {
+                type Model = #model;
+            }
+        });
 
-            let schema_has = models.iter().map(|model| quote! {
-                impl ::hell_orm::schema::SchemaHas<#model> for #ident {}
-            });
+        let schema_tuple = models.iter().rev().fold(quote! {()}, |acc, model| quote! { (#model, #acc) });
 
-            let schema_tuple = models.iter().rev().fold(quote! {()}, |acc, model| quote! { (#model, #acc) });
+        return TokenStream::from(quote! {
+            impl ::hell_orm::schema::Schema for #ident {
+                fn create(connection: &mut ::hell_orm::__macro_export::rusqlite::Connection) -> Result<(), ::hell_orm::error::Error> {
+                    <#schema_tuple as ::hell_orm::schema::Schema>::create(connection)
+                }
 
-            return TokenStream::from(quote! {
-                impl ::hell_orm::schema::Schema for #ident {
-                    fn create(connection: &mut ::hell_orm::__macro_export::rusqlite::Connection) -> Result<(), ::hell_orm::error::Error> {
-                        <#schema_tuple as ::hell_orm::schema::Schema>::create(connection)
-                    }
+                fn verify(connection: &::hell_orm::__macro_export::rusqlite::Connection) -> Result<(), ::hell_orm::error::Error> {
+                    <#schema_tuple as ::hell_orm::schema::Schema>::verify(connection)
                 }
+            }
 
-                #(#schema_has)*
-            });
-        }
+            #(#schema_has)*
+
+            #(#tables)*
+        });
     }
 
     TokenStream::from(syn::Error::new(input.ident.span(), "Model can only be derived for structs with zero fields").to_compile_error())
 }
 
+#[proc_macro_derive(Projection, attributes(projection))]
+pub fn derive_projection(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    if let Data::Struct(data) = &input.data && let Fields::Named(fields) = &data.fields {
+        return match Projection::parse(&input, fields) {
+            Ok(projection) => TokenStream::from(quote! { #projection }),
+            Err(err) => TokenStream::from(err.to_compile_error()),
+        };
+    }
+
+    TokenStream::from(syn::Error::new(input.ident.span(), "Projection can only be derived for structs with named fields").to_compile_error())
+}
+
+#[proc_macro_derive(FromRow, attributes(row))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    if let Data::Struct(data) = &input.data && let Fields::Named(fields) = &data.fields {
+        return match FromRow::parse(&input.ident, fields) {
+            Ok(from_row) => TokenStream::from(quote! { #from_row }),
+            Err(err) => TokenStream::from(err.to_compile_error()),
+        };
+    }
+
+    TokenStream::from(syn::Error::new(input.ident.span(), "FromRow can only be derived for structs with named fields").to_compile_error())
+}
+
+/// Builds a typed join condition, `on!(Post::user == User::id)` compares `Post::USER_COL` with `User::ID_COL`.
+#[proc_macro]
+pub fn on(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ExprBinary);
+
+    let column = |expr: &Expr| -> Result<ExprPath, syn::Error> {
+        let Expr::Path(path) = expr else {
+            return Err(syn::Error::new(expr.span(), "expected a column such as Model::field"));
+        };
+
+        let mut path = path.clone();
+        let qualified = path.path.segments.len() > 1;
+
+        match path.path.segments.last_mut() {
+            Some(segment) if qualified => segment.ident = model::column_ident(&segment.ident),
+            _ => return Err(syn::Error::new(path.span(), "expected a column such as Model::field")),
+        }
+
+        Ok(path)
+    };
+
+    if !matches!(input.op, BinOp::Eq(_)) {
+        return TokenStream::from(syn::Error::new(input.op.span(), "joins can only compare columns with ==").to_compile_error());
+    }
+
+    match (column(&input.left), column(&input.right)) {
+        (Ok(left), Ok(right)) => TokenStream::from(quote! {
+            ::hell_orm::schema::join::On::new(#left, #right)
+        }),
+        (Err(err), _) | (_, Err(err)) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+/// Checks a raw SELECT statement against a schema at compile time, `query_as!(Schema, "SELECT * FROM users WHERE id = ?", id)`.
+///
+/// Tables and columns must belong to the models listed in `#[models(...)]`. Selected columns, `table.*` and
+/// `COUNT(...)` decode into their Rust types, and placeholders compared with a column only accept values of its type.
+#[proc_macro]
+pub fn query_as(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as QueryAs);
+
+    match input.expand() {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
 
@@ -1,6 +1,9 @@
+use crate::relation::Relations;
+
+use syn::ext::IdentExt;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Token, Field, Attribute, Type, PathArguments, GenericArgument};
+use syn::{Token, Field, Attribute, Type, Ident, PathArguments, GenericArgument};
 use quote::{quote, ToTokens};
 
 
@@ -8,22 +11,28 @@ pub struct Model<'a> {
     fields: &'a Punctuated<Field, Token![,]>,
     attributes: &'a [Attribute],
     table_name: &'a str,
+    relations: &'a Relations<'a>,
 }
 
 impl<'a> Model<'a> {
-    pub fn new(fields: &'a Punctuated<Field, Token![,]>, attributes: &'a [Attribute], table_name: &'a str) -> Model<'a> {
+    pub fn new(fields: &'a Punctuated<Field, Token![,]>, attributes: &'a [Attribute], table_name: &'a str, relations: &'a Relations<'a>) -> Model<'a> {
         Model {
             fields,
             attributes,
             table_name,
+            relations,
         }
     }
 
+    pub fn is_soft_delete(&self) -> bool {
+        self.attributes.iter().any(|attribute| attribute.path().is_ident("soft_delete"))
+    }
+
     pub fn columns(&self) -> impl Iterator<Item = proc_macro2::TokenStream> {
         self.fields.iter()
             .map(|field| {
-                let name = field.ident.as_ref().map(|ident| ident.to_string());
-                let sqlite_type = FieldType::new(&field).sqlite_type();
+                let name = field.ident.as_ref().map(|ident| ident.unraw().to_string());
+                let sqlite_type = FieldType::new(field).sqlite_type();
 
                 match sqlite_type {
                     Ok(ty) => {
@@ -43,15 +52,152 @@ impl<'a> ToTokens for Model<'a> {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let table_name = &self.table_name;
         let columns = self.columns();
+        let foreign_keys = self.relations.foreign_keys();
+
+        let soft_delete = self.is_soft_delete().then(|| quote! {
+            const SOFT_DELETE: bool = true;
+        });
+
+        let deleted_at = self.is_soft_delete().then(|| quote! { ("deleted_at", "INTEGER"), });
+
+        let version = self.fields.iter()
+            .find(|field| field.attrs.iter().any(|attr| attr.path().is_ident("version")))
+            .and_then(|field| field.ident.as_ref())
+            .map(|ident| {
+                let name = ident.unraw().to_string();
+
+                quote! {
+                    const VERSION: ::std::option::Option<&'static str> = Some(#name);
+                }
+            });
 
         tokens.extend(quote! {
             const NAME: &'static str = #table_name;
 
-            const COLUMNS: &'static [(&'static str, &'static str)] = &[#(#columns,)*];
+            const COLUMNS: &'static [(&'static str, &'static str)] = &[#(#columns,)* #deleted_at];
+
+            #soft_delete
+
+            #version
+
+            const FOREIGN_KEYS: &'static [(&'static str, &'static str, &'static str)] = &[#(#foreign_keys,)*];
         });
     }
 }
 
+pub struct PrimaryKey<'a> {
+    fields: &'a Punctuated<Field, Token![,]>,
+    model: &'a Ident,
+}
+
+impl<'a> PrimaryKey<'a> {
+    pub fn new(fields: &'a Punctuated<Field, Token![,]>, model: &'a Ident) -> PrimaryKey<'a> {
+        PrimaryKey {
+            fields,
+            model,
+        }
+    }
+}
+
+impl<'a> ToTokens for PrimaryKey<'a> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let model = &self.model;
+
+        if let Some(field) = self.fields.iter().find(|field| field.attrs.iter().any(|attr| attr.path().is_ident("primary_key"))) {
+            let ident = &field.ident;
+            let ty = &field.ty;
+            let name = field.ident.as_ref().map(|ident| ident.unraw().to_string());
+
+            tokens.extend(quote! {
+                impl ::hell_orm::schema::PrimaryKey for #model {
+                    type Key = #ty;
+
+                    const PRIMARY_KEY: &'static str = #name;
+
+                    fn primary_key(&self) -> &#ty {
+                        &self.#ident
+                    }
+                }
+            });
+        }
+    }
+}
+
+pub struct Version<'a> {
+    fields: &'a Punctuated<Field, Token![,]>,
+    model: &'a Ident,
+}
+
+impl<'a> Version<'a> {
+    pub fn new(fields: &'a Punctuated<Field, Token![,]>, model: &'a Ident) -> Version<'a> {
+        Version {
+            fields,
+            model,
+        }
+    }
+}
+
+impl<'a> ToTokens for Version<'a> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let model = &self.model;
+
+        if let Some(field) = self.fields.iter().find(|field| field.attrs.iter().any(|attr| attr.path().is_ident("version"))) {
+            let ty = &field.ty;
+            let name = field.ident.as_ref().map(|ident| ident.unraw().to_string());
+
+            tokens.extend(quote! {
+                impl ::hell_orm::schema::Versioned for #model {
+                    type Version = #ty;
+
+                    const VERSION_COLUMN: &'static str = #name;
+                }
+            });
+        }
+    }
+}
+
+pub struct Columns<'a> {
+    fields: &'a Punctuated<Field, Token![,]>,
+    model: &'a Ident,
+}
+
+impl<'a> Columns<'a> {
+    pub fn new(fields: &'a Punctuated<Field, Token![,]>, model: &'a Ident) -> Columns<'a> {
+        Columns {
+            fields,
+            model,
+        }
+    }
+}
+
+impl<'a> ToTokens for Columns<'a> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let model = &self.model;
+
+        let columns = self.fields.iter()
+            .filter_map(|field| field.ident.as_ref().map(|ident| (ident, &field.ty)))
+            .map(|(ident, ty)| {
+                let name = ident.unraw().to_string();
+                let column = column_ident(ident);
+
+                quote! {
+                    pub const #column: ::hell_orm::schema::column::Column<#model, #ty> = ::hell_orm::schema::column::Column::new(#name);
+                }
+            });
+
+        tokens.extend(quote! {
+            impl #model {
+                #(#columns)*
+            }
+        });
+    }
+}
+
+/// The name of the typed column constant generated for a field, `name` becomes `NAME_COL`.
+pub fn column_ident(field: &Ident) -> Ident {
+    Ident::new(&format!("{}_COL", field.unraw().to_string().to_uppercase()), field.span())
+}
+
 pub struct FieldType<'a> {
     field: &'a Field,
 }
@@ -64,14 +210,12 @@ impl<'a> FieldType<'a> {
     }
 
     fn inner_option_type(&self) -> &Type {
-        if let Type::Path(path) = &self.field.ty {
-            if let Some(segment) = path.path.segments.last().and_then(|last| (last.ident == "Option").then_some(last)) {
-                if let PathArguments::AngleBracketed(arguments) = &segment.arguments {
-                    if let Some(GenericArgument::Type(ty)) = arguments.args.first() {
-                        return ty;
-                    }
-                }
-            }
+        if let Type::Path(path) = &self.field.ty
+            && let Some(segment) = path.path.segments.last().and_then(|last| (last.ident == "Option").then_some(last))
+            && let PathArguments::AngleBracketed(arguments) = &segment.arguments
+            && let Some(GenericArgument::Type(ty)) = arguments.args.first()
+        {
+            return ty;
         }
 
         &self.field.ty
@@ -89,18 +233,24 @@ impl<'a> FieldType<'a> {
 
     fn sqlite_type(&self) -> Result<String, syn::Error> {
         let mut attributes = if let Type::Path(path) = &self.field.ty && path.path.segments.last().map(|last| last.ident == "Option").unwrap_or_default() {
-            String::from(" NOT NULL")
-        } else {
             String::new()
+        } else {
+            String::from(" NOT NULL")
         };
 
         for (attribute_name, sqlite_type) in [("primary_key", " PRIMARY KEY"), ("auto_increment", " AUTOINCREMENT"), ("unique", " UNIQUE")] {
             if self.field.attrs.iter().any(|attr| attr.path().is_ident(attribute_name)) {
-                attributes.extend(sqlite_type.chars());
+                attributes.push_str(sqlite_type);
             }
         }
 
-        self.raw_type().map(|ty| format!("{}{}", ty, attributes))
+        let ty = self.raw_type()?;
+
+        if ty != "INTEGER" && self.field.attrs.iter().any(|attr| attr.path().is_ident("version")) {
+            return Err(syn::Error::new(self.field.ty.span(), "#[version] requires an integer field"));
+        }
+
+        Ok(format!("{}{}", ty, attributes))
     }
 }
 
@@ -0,0 +1,69 @@
+use crate::model::column_ident;
+
+use syn::ext::IdentExt;
+use syn::{DeriveInput, FieldsNamed, Path};
+use quote::{quote, ToTokens};
+
+
+pub struct Projection<'a> {
+    input: &'a DeriveInput,
+    fields: &'a FieldsNamed,
+    model: Path,
+}
+
+impl<'a> Projection<'a> {
+    pub fn parse(input: &'a DeriveInput, fields: &'a FieldsNamed) -> Result<Projection<'a>, syn::Error> {
+        let attribute = input.attrs.iter()
+            .find(|attribute| attribute.path().is_ident("projection"))
+            .ok_or_else(|| syn::Error::new(input.ident.span(), "Projection derive requires #[projection(Model)] attribute"))?;
+
+        Ok(Projection {
+            input,
+            fields,
+            model: attribute.parse_args()?,
+        })
+    }
+}
+
+impl<'a> ToTokens for Projection<'a> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let ident = &self.input.ident;
+        let model = &self.model;
+
+        let fields = self.fields.named.iter()
+            .filter_map(|field| field.ident.as_ref().map(|ident| (ident, &field.ty)))
+            .collect::<Vec<_>>();
+
+        let names = fields.iter().map(|(ident, _)| ident.unraw().to_string());
+
+        let row_fields = fields.iter().enumerate().map(|(index, (ident, _))| quote! {
+            #ident: row.get(offset + #index)?
+        });
+
+        // every field has to name a column of the model holding the same type
+        let checks = fields.iter().map(|(ident, ty)| {
+            let column = column_ident(ident);
+
+            quote! {
+                let _: ::hell_orm::schema::column::Column<#model, #ty> = #model::#column;
+            }
+        });
+
+        tokens.extend(quote! {
+            impl ::hell_orm::schema::select::Projection<#model> for #ident {
+                const COLUMNS: &'static [&'static str] = &[#(#names,)*];
+
+                fn from_row(row: &::hell_orm::__macro_export::rusqlite::Row, offset: usize) -> ::std::result::Result<Self, ::hell_orm::__macro_export::rusqlite::Error> {
+                    Ok(Self {
+                        #(#row_fields,)*
+                    })
+                }
+            }
+
+            const _: () = {
+                #(#checks)*
+            };
+        });
+    }
+}
+
@@ -1,5 +1,63 @@
+use crate::insert::field::ModelField;
+use crate::model::column_ident;
 
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+use syn::{Token, Ident, Field};
+use quote::{quote, format_ident, ToTokens};
 
 
+/// Methods of the generated query builder, a field filter with one of these names is generated as `where_{name}` instead.
+const RESERVED: &[&str] = &[
+    "aggregate", "all", "avg", "count", "delete", "distinct", "exists", "filter", "first", "for_each", "get", "get_by_id",
+    "group_by", "include", "join", "last", "left_join", "limit", "max", "min", "offset", "only_deleted", "order_by",
+    "paginate_after", "recursive", "select", "select_as", "subquery", "sum", "try_for_each", "update", "with", "with_deleted",
+];
 
+pub struct QueryStructFunctions<'a> {
+    fields: Vec<ModelField<'a>>,
+    query_ident: &'a Ident,
+    model: &'a Ident,
+}
+
+impl<'a> QueryStructFunctions<'a> {
+    pub fn new(fields: &'a Punctuated<Field, Token![,]>, query_ident: &'a Ident, model: &'a Ident) -> QueryStructFunctions<'a> {
+        QueryStructFunctions {
+            fields: fields.iter().map(ModelField::new).collect(),
+            query_ident,
+            model,
+        }
+    }
+}
+
+impl<'a> ToTokens for QueryStructFunctions<'a> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let query_ident = &self.query_ident;
+        let model = &self.model;
+
+        for field in self.fields.iter() {
+            let ident = field.ident().as_ref().expect("expected a named field");
+            let type_ = field.type_();
+            let column = column_ident(ident);
+            let name = ident.unraw().to_string();
+
+            let (method, doc) = if RESERVED.contains(&name.as_str()) {
+                let doc = format!(" Filters on `{}`, which is prefixed as the builder already has a `{}` method.", name, name);
+
+                (format_ident!("where_{}", name, span = ident.span()), quote! { #[doc = #doc] })
+            } else {
+                (ident.clone(), quote! {})
+            };
+
+            tokens.extend(quote! {
+                #doc
+                pub fn #method(self, value: ::hell_orm::schema::query::Where<#type_>) -> #query_ident<'a, S> {
+                    #query_ident {
+                        builder: self.builder.filter(#model::#column.compare(value)),
+                    }
+                }
+            });
+        }
+    }
+}
 
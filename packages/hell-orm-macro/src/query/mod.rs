@@ -1,33 +1,268 @@
 mod builder;
 mod field;
 
+use builder::QueryStructFunctions;
+
 use syn::{DeriveInput, FieldsNamed};
-use quote::{quote, ToTokens};
+use quote::{quote, format_ident, ToTokens};
 
 
 pub struct Query<'a> {
     input: &'a DeriveInput,
     fields: &'a FieldsNamed,
+    table_name: &'a str,
 }
 
 impl<'a> Query<'a> {
-    pub fn new(input: &'a DeriveInput, fields: &'a FieldsNamed) -> Query<'a> {
+    pub fn new(input: &'a DeriveInput, fields: &'a FieldsNamed, table_name: &'a str) -> Query<'a> {
         Query {
             input,
             fields,
+            table_name,
         }
     }
 }
 
 impl<'a> ToTokens for Query<'a> {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let query_ident = format_ident!("__{}QueryBuilder", self.input.ident);
         let ident = &self.input.ident;
+        let table_name = &self.table_name;
+
+        let query_struct_functions = QueryStructFunctions::new(&self.fields.named, &query_ident, &self.input.ident);
+
+        let soft_delete = self.input.attrs.iter().any(|attribute| attribute.path().is_ident("soft_delete")).then(|| quote! {
+            pub fn with_deleted(self) -> #query_ident<'a, S> {
+                #query_ident {
+                    builder: self.builder.with_deleted(),
+                }
+            }
+
+            pub fn only_deleted(self) -> #query_ident<'a, S> {
+                #query_ident {
+                    builder: self.builder.only_deleted(),
+                }
+            }
+        });
+
+        let primary_key = self.fields.named.iter().any(|field| field.attrs.iter().any(|attr| attr.path().is_ident("primary_key"))).then(|| quote! {
+            /// Loads the `Child` rows of every result in one batched query.
+            pub fn include<Child>(self) -> ::hell_orm::schema::relation::IncludeBuilder<'a, #ident, Child, S>
+            where
+                Child: ::hell_orm::schema::relation::BelongsTo<#ident>,
+                S: ::hell_orm::schema::SchemaHas<Child>
+            {
+                self.builder.include()
+            }
+
+            /// The first matching row in `order_by` order, ties and unordered queries going to the lowest primary key.
+            pub fn first(self) -> ::std::result::Result<::std::option::Option<#ident>, ::hell_orm::error::Error> {
+                self.builder.first()
+            }
+
+            /// The last matching row in `order_by` order, ties and unordered queries going to the highest primary key.
+            ///
+            /// With `limit` or `offset` set it is the last of the rows a select would return.
+            pub fn last(self) -> ::std::result::Result<::std::option::Option<#ident>, ::hell_orm::error::Error> {
+                self.builder.last()
+            }
+
+            /// The matching row whose primary key is `id`.
+            pub fn get_by_id(self, id: <#ident as ::hell_orm::schema::PrimaryKey>::Key) -> ::std::result::Result<::std::option::Option<#ident>, ::hell_orm::error::Error> {
+                self.builder.get_by_id(id)
+            }
+
+            /// Fetches the `page_size` rows following `cursor`, or the first page when `cursor` is `None`.
+            pub fn paginate_after(self, cursor: ::std::option::Option<&::hell_orm::schema::page::Cursor>, page_size: usize) -> ::std::result::Result<::hell_orm::schema::page::Page<#ident>, ::hell_orm::error::Error> {
+                self.builder.paginate_after(cursor, page_size)
+            }
+        });
 
         tokens.extend(quote! {
-            //impl ::hell_orm::schema::query::Query for #ident {
-            //}
+            pub struct #query_ident<'a, S> {
+                builder: ::hell_orm::schema::query::QueryBuilder<'a, #ident, S>,
+            }
+
+            impl<'a, S> #query_ident<'a, S> {
+                #query_struct_functions
+
+                pub fn filter(self, condition: ::hell_orm::schema::condition::Condition<#ident>) -> #query_ident<'a, S> {
+                    #query_ident {
+                        builder: self.builder.filter(condition),
+                    }
+                }
+
+                /// Removes duplicate results, mostly useful together with `select`.
+                pub fn distinct(self) -> #query_ident<'a, S> {
+                    #query_ident {
+                        builder: self.builder.distinct(),
+                    }
+                }
+
+                /// Sorts the results by `order`, after any earlier sort keys.
+                pub fn order_by(self, order: ::hell_orm::schema::column::Order<#ident>) -> #query_ident<'a, S> {
+                    #query_ident {
+                        builder: self.builder.order_by(order),
+                    }
+                }
+
+                pub fn limit(self, limit: usize) -> #query_ident<'a, S> {
+                    #query_ident {
+                        builder: self.builder.limit(limit),
+                    }
+                }
+
+                pub fn offset(self, offset: usize) -> #query_ident<'a, S> {
+                    #query_ident {
+                        builder: self.builder.offset(offset),
+                    }
+                }
+
+                #soft_delete
+
+                #primary_key
+
+                /// The first matching row, after `offset` if one is set.
+                pub fn get(self) -> ::std::result::Result<::std::option::Option<#ident>, ::hell_orm::error::Error> {
+                    self.builder.get()
+                }
+
+                pub fn all(self) -> ::std::result::Result<::std::vec::Vec<#ident>, ::hell_orm::error::Error> {
+                    self.builder.all()
+                }
+
+                /// Whether any row matches, without loading it.
+                pub fn exists(self) -> ::std::result::Result<bool, ::hell_orm::error::Error> {
+                    self.builder.exists()
+                }
+
+                /// Calls `f` with every matching row as it is read, without collecting the results.
+                pub fn for_each(self, f: impl FnMut(#ident)) -> ::std::result::Result<(), ::hell_orm::error::Error> {
+                    self.builder.for_each(f)
+                }
+
+                /// Calls `f` with every matching row as it is read, stopping at the first error.
+                pub fn try_for_each<E>(self, f: impl FnMut(#ident) -> ::std::result::Result<(), E>) -> ::std::result::Result<(), E>
+                where
+                    E: ::std::convert::From<::hell_orm::error::Error>
+                {
+                    self.builder.try_for_each(f)
+                }
+
+                /// Loads only the columns in `selection`, such as a tuple of columns `(User::ID_COL, User::NAME_COL)`.
+                pub fn select<Sel>(self, selection: Sel) -> ::std::result::Result<::std::vec::Vec<Sel::Output>, ::hell_orm::error::Error>
+                where
+                    Sel: ::hell_orm::schema::select::Selection<#ident>
+                {
+                    self.builder.select(selection)
+                }
+
+                /// Turns the query into a subquery selecting `selection`, to use in the filters of another query.
+                pub fn subquery<Sel>(self, selection: Sel) -> ::hell_orm::schema::subquery::Subquery<Sel::Output>
+                where
+                    Sel: ::hell_orm::schema::select::Selectable<#ident>
+                {
+                    self.builder.subquery(selection)
+                }
+
+                /// Adds the common table expression `with` ahead of the query, see `Subquery::named`.
+                pub fn with<T>(self, with: ::hell_orm::schema::subquery::With<T>) -> #query_ident<'a, S> {
+                    #query_ident {
+                        builder: self.builder.with(with),
+                    }
+                }
+
+                /// Loads only the columns of the projection `P`.
+                pub fn select_as<P>(self) -> ::std::result::Result<::std::vec::Vec<P>, ::hell_orm::error::Error>
+                where
+                    P: ::hell_orm::schema::select::Projection<#ident>
+                {
+                    self.builder.select_as()
+                }
+
+                /// Evaluates `aggregate` over the matching rows.
+                pub fn aggregate<T>(self, aggregate: ::hell_orm::schema::aggregate::Aggregate<#ident, T>) -> ::std::result::Result<T, ::hell_orm::error::Error>
+                where
+                    T: ::hell_orm::__macro_export::rusqlite::types::FromSql
+                {
+                    self.builder.aggregate(aggregate)
+                }
+
+                pub fn count(self) -> ::std::result::Result<i64, ::hell_orm::error::Error> {
+                    self.builder.count()
+                }
+
+                pub fn sum<T>(self, column: ::hell_orm::schema::column::Column<#ident, T>) -> ::std::result::Result<::std::option::Option<T>, ::hell_orm::error::Error>
+                where
+                    T: ::hell_orm::__macro_export::rusqlite::types::FromSql + ::hell_orm::schema::aggregate::Numeric
+                {
+                    self.builder.sum(column)
+                }
+
+                pub fn avg<T>(self, column: ::hell_orm::schema::column::Column<#ident, T>) -> ::std::result::Result<::std::option::Option<f64>, ::hell_orm::error::Error>
+                where
+                    T: ::hell_orm::schema::aggregate::Numeric
+                {
+                    self.builder.avg(column)
+                }
+
+                pub fn min<T>(self, column: ::hell_orm::schema::column::Column<#ident, T>) -> ::std::result::Result<::std::option::Option<T>, ::hell_orm::error::Error>
+                where
+                    T: ::hell_orm::__macro_export::rusqlite::types::FromSql
+                {
+                    self.builder.min(column)
+                }
+
+                pub fn max<T>(self, column: ::hell_orm::schema::column::Column<#ident, T>) -> ::std::result::Result<::std::option::Option<T>, ::hell_orm::error::Error>
+                where
+                    T: ::hell_orm::__macro_export::rusqlite::types::FromSql
+                {
+                    self.builder.max(column)
+                }
+
+                /// Groups the matching rows by `column`, to select aggregates per group.
+                pub fn group_by<T>(self, column: ::hell_orm::schema::column::Column<#ident, T>) -> ::hell_orm::schema::aggregate::GroupBuilder<'a, #ident, S> {
+                    self.builder.group_by(column)
+                }
+
+                /// Joins every result with the `Other` row matching `on`, skipping results without one.
+                pub fn join<Other>(self, on: ::hell_orm::schema::join::On<#ident, Other>) -> ::hell_orm::schema::join::JoinBuilder<'a, #ident, Other, S, ::hell_orm::schema::join::Inner>
+                where
+                    Other: ::hell_orm::schema::Model,
+                    S: ::hell_orm::schema::SchemaHas<Other>
+                {
+                    self.builder.join(on)
+                }
+
+                /// Joins every result with the `Other` row matching `on`, if there is one.
+                pub fn left_join<Other>(self, on: ::hell_orm::schema::join::On<#ident, Other>) -> ::hell_orm::schema::join::JoinBuilder<'a, #ident, Other, S, ::hell_orm::schema::join::Left>
+                where
+                    Other: ::hell_orm::schema::Model,
+                    S: ::hell_orm::schema::SchemaHas<Other>
+                {
+                    self.builder.left_join(on)
+                }
+
+                /// Walks a tree stored in the model, starting from the matching rows and following `step` from every row found to the next.
+                pub fn recursive(self, step: ::hell_orm::schema::join::On<#ident, #ident>) -> ::hell_orm::schema::recursive::RecursiveBuilder<'a, #ident, S> {
+                    self.builder.recursive(step)
+                }
+
+                pub fn delete(self) -> ::std::result::Result<usize, ::hell_orm::error::Error> {
+                    self.builder.delete()
+                }
+            }
+
+            impl ::hell_orm::schema::query::Query for #ident {
+                type Builder<'a, S> = #query_ident<'a, S>;
+
+                fn builder<'a, S>(connection: &'a mut ::hell_orm::__macro_export::rusqlite::Connection) -> <Self as ::hell_orm::schema::query::Query>::Builder<'a, S> {
+                    #query_ident {
+                        builder: ::hell_orm::schema::query::QueryBuilder::new(connection, #table_name),
+                    }
+                }
+            }
         });
     }
 }
 
-
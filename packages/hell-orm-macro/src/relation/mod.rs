@@ -0,0 +1,254 @@
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{DeriveInput, FieldsNamed, LitStr, Path, Ident, Attribute, Type};
+use quote::{quote, format_ident, ToTokens};
+
+
+/// Parses a generated method name, which has to be a plain identifier and not a keyword.
+fn method_ident(name: &str, span: proc_macro2::Span, hint: &str) -> Result<Ident, syn::Error> {
+    syn::parse_str::<Ident>(name)
+        .map(|ident| Ident::new(&ident.to_string(), span))
+        .map_err(|_| syn::Error::new(span, format!("`{}` is not a valid method name, {}", name, hint)))
+}
+
+pub struct BelongsTo {
+    parent: Path,
+    key: Ident,
+    /// Whether the key is an `Option`, a NULL key meaning the row has no parent.
+    optional: bool,
+    /// The method on the parent returning its children, named after the child's table unless `as = "..."` is given.
+    children: Ident,
+}
+
+impl BelongsTo {
+    fn parse(attribute: &Attribute, fields: &FieldsNamed, table_name: &str) -> Result<BelongsTo, syn::Error> {
+        let mut parent = None;
+        let mut key = None;
+        let mut name = None;
+
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("key") {
+                key = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("as") {
+                name = Some(meta.value()?.parse::<LitStr>()?);
+            } else if parent.is_none() && key.is_none() && name.is_none() {
+                parent = Some(meta.path);
+            } else {
+                return Err(meta.error("expected `key = \"field\"` or `as = \"name\"`"));
+            }
+
+            Ok(())
+        })?;
+
+        let span = attribute.span();
+
+        let parent = parent.ok_or_else(|| syn::Error::new(span, "expected #[belongs_to(Model, key = \"field\")]"))?;
+        let key = key.ok_or_else(|| syn::Error::new(span, "#[belongs_to] requires a key = \"field\" argument"))?;
+
+        let field = fields.named.iter()
+            .find(|field| field.ident.as_ref().is_some_and(|ident| ident.unraw() == key.value().as_str()))
+            .ok_or_else(|| syn::Error::new(key.span(), format!("no field named `{}`", key.value())))?;
+
+        let optional = matches!(&field.ty, Type::Path(path) if path.path.segments.last().is_some_and(|last| last.ident == "Option"));
+
+        let children = match name {
+            Some(name) => method_ident(&name.value(), name.span(), "expected an identifier")?,
+            None => method_ident(table_name, span, "name the children method with #[belongs_to(Model, key = \"field\", as = \"name\")]")?,
+        };
+
+        Ok(BelongsTo {
+            parent,
+            key: field.ident.clone().expect("named fields have an ident"),
+            optional,
+            children,
+        })
+    }
+}
+
+pub struct ManyToMany {
+    other: Path,
+    through: Path,
+    /// The snake case name of the related model, used to name the link and unlink methods.
+    name: String,
+    /// The method returning the related rows, `as = "..."` or the plural of `name`.
+    related: Ident,
+}
+
+impl ManyToMany {
+    fn parse(attribute: &Attribute) -> Result<ManyToMany, syn::Error> {
+        let mut other = None;
+        let mut through = None;
+        let mut related = None;
+
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("through") {
+                through = Some(meta.value()?.parse::<Path>()?);
+            } else if meta.path.is_ident("as") {
+                related = Some(meta.value()?.parse::<LitStr>()?);
+            } else if other.is_none() && through.is_none() && related.is_none() {
+                other = Some(meta.path);
+            } else {
+                return Err(meta.error("expected `through = JoinModel` or `as = \"name\"`"));
+            }
+
+            Ok(())
+        })?;
+
+        let span = attribute.span();
+
+        let other = other.ok_or_else(|| syn::Error::new(span, "expected #[many_to_many(Model, through = JoinModel)]"))?;
+        let through = through.ok_or_else(|| syn::Error::new(span, "#[many_to_many] requires a through = JoinModel argument"))?;
+        let name = snake_case(&other);
+
+        let related = match related {
+            Some(related) => method_ident(&related.value(), related.span(), "expected an identifier")?,
+            None => method_ident(&plural(&name), other.span(), "name it with #[many_to_many(Model, through = JoinModel, as = \"name\")]")?,
+        };
+
+        Ok(ManyToMany {
+            other,
+            through,
+            name,
+            related,
+        })
+    }
+}
+
+fn snake_case(path: &Path) -> String {
+    let ident = path.segments.last().map(|segment| segment.ident.to_string()).unwrap_or_default();
+
+    ident.chars().enumerate().fold(String::new(), |mut name, (index, char)| {
+        if char.is_uppercase() && index > 0 {
+            name.push('_');
+        }
+
+        name.extend(char.to_lowercase());
+        name
+    })
+}
+
+/// English plural of a snake case name, covering the regular `-s`, `-es` and `-ies` endings.
+fn plural(name: &str) -> String {
+    let consonant_y = name.strip_suffix('y').filter(|stem| stem.chars().last().is_some_and(|char| !"aeiou".contains(char)));
+
+    if let Some(stem) = consonant_y {
+        format!("{}ies", stem)
+    } else if ["s", "x", "z", "ch", "sh"].iter().any(|suffix| name.ends_with(suffix)) {
+        format!("{}es", name)
+    } else {
+        format!("{}s", name)
+    }
+}
+
+pub struct Relations<'a> {
+    input: &'a DeriveInput,
+    belongs_to: Vec<BelongsTo>,
+    many_to_many: Vec<ManyToMany>,
+}
+
+impl<'a> Relations<'a> {
+    pub fn parse(input: &'a DeriveInput, fields: &'a FieldsNamed, table_name: &'a str) -> Result<Relations<'a>, syn::Error> {
+        let belongs_to = input.attrs.iter()
+            .filter(|attribute| attribute.path().is_ident("belongs_to"))
+            .map(|attribute| BelongsTo::parse(attribute, fields, table_name))
+            .collect::<Result<Vec<BelongsTo>, syn::Error>>()?;
+
+        let many_to_many = input.attrs.iter()
+            .filter(|attribute| attribute.path().is_ident("many_to_many"))
+            .map(ManyToMany::parse)
+            .collect::<Result<Vec<ManyToMany>, syn::Error>>()?;
+
+        Ok(Relations {
+            input,
+            belongs_to,
+            many_to_many,
+        })
+    }
+
+    pub fn foreign_keys(&self) -> impl Iterator<Item = proc_macro2::TokenStream> {
+        self.belongs_to.iter()
+            .map(|belongs_to| {
+                let parent = &belongs_to.parent;
+                let key = belongs_to.key.unraw().to_string();
+
+                quote! { (#key, <#parent as ::hell_orm::schema::Model>::NAME, <#parent as ::hell_orm::schema::PrimaryKey>::PRIMARY_KEY) }
+            })
+    }
+}
+
+impl<'a> ToTokens for Relations<'a> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let ident = &self.input.ident;
+
+        for BelongsTo { parent, key, optional, children } in self.belongs_to.iter() {
+            let column = key.unraw().to_string();
+
+            let foreign_key = if *optional {
+                quote! { self.#key.as_ref() }
+            } else {
+                quote! { ::std::option::Option::Some(&self.#key) }
+            };
+
+            tokens.extend(quote! {
+                impl ::hell_orm::schema::relation::BelongsTo<#parent> for #ident {
+                    const FOREIGN_KEY: &'static str = #column;
+
+                    fn foreign_key(&self) -> ::std::option::Option<&<#parent as ::hell_orm::schema::PrimaryKey>::Key> {
+                        #foreign_key
+                    }
+                }
+
+                impl #ident {
+                    pub fn #key<S>(&self, db: &mut ::hell_orm::Database<S>) -> ::std::result::Result<::std::option::Option<#parent>, ::hell_orm::error::Error>
+                    where
+                        S: ::hell_orm::schema::Schema + ::hell_orm::schema::SchemaHas<#parent>
+                    {
+                        db.parent::<#ident, #parent>(self)
+                    }
+                }
+
+                impl #parent {
+                    pub fn #children<S>(&self, db: &mut ::hell_orm::Database<S>) -> ::std::result::Result<::std::vec::Vec<#ident>, ::hell_orm::error::Error>
+                    where
+                        S: ::hell_orm::schema::Schema + ::hell_orm::schema::SchemaHas<#ident>
+                    {
+                        db.children::<#parent, #ident>(self)
+                    }
+                }
+            });
+        }
+
+        for many_to_many in self.many_to_many.iter() {
+            let ManyToMany { other, through, name, related } = many_to_many;
+
+            let link = format_ident!("add_{}", name);
+            let unlink = format_ident!("remove_{}", name);
+
+            tokens.extend(quote! {
+                impl #ident {
+                    pub fn #related<S>(&self, db: &mut ::hell_orm::Database<S>) -> ::std::result::Result<::std::vec::Vec<#other>, ::hell_orm::error::Error>
+                    where
+                        S: ::hell_orm::schema::Schema + ::hell_orm::schema::SchemaHas<#ident> + ::hell_orm::schema::SchemaHas<#through> + ::hell_orm::schema::SchemaHas<#other>
+                    {
+                        db.related::<#ident, #through, #other>(self)
+                    }
+
+                    pub fn #link<S>(&self, db: &mut ::hell_orm::Database<S>, other: &#other) -> ::std::result::Result<(), ::hell_orm::error::Error>
+                    where
+                        S: ::hell_orm::schema::Schema + ::hell_orm::schema::SchemaHas<#ident> + ::hell_orm::schema::SchemaHas<#through> + ::hell_orm::schema::SchemaHas<#other>
+                    {
+                        db.link::<#ident, #through, #other>(self, other)
+                    }
+
+                    pub fn #unlink<S>(&self, db: &mut ::hell_orm::Database<S>, other: &#other) -> ::std::result::Result<usize, ::hell_orm::error::Error>
+                    where
+                        S: ::hell_orm::schema::Schema + ::hell_orm::schema::SchemaHas<#ident> + ::hell_orm::schema::SchemaHas<#through> + ::hell_orm::schema::SchemaHas<#other>
+                    {
+                        db.unlink::<#ident, #through, #other>(self, other)
+                    }
+                }
+            });
+        }
+    }
+}
+
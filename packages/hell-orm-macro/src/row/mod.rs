@@ -0,0 +1,126 @@
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{Ident, Type, Field, FieldsNamed, LitInt};
+use quote::{quote, ToTokens};
+
+
+/// Where a field reads its value from.
+enum Source {
+    /// The column with the field's name.
+    Name(String),
+    /// The column at this index, counted from the decoding offset.
+    Index(usize),
+    /// A nested `FromRow` type, decoded from the same row starting at this index.
+    Flatten(usize),
+}
+
+struct RowField<'a> {
+    ident: &'a Ident,
+    ty: &'a Type,
+    source: Source,
+}
+
+impl<'a> RowField<'a> {
+    fn parse(field: &'a Field, ident: &'a Ident) -> Result<RowField<'a>, syn::Error> {
+        let mut index = None;
+        let mut flatten = false;
+
+        for attribute in field.attrs.iter().filter(|attribute| attribute.path().is_ident("row")) {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("index") {
+                    index = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<usize>()?);
+                    Ok(())
+                } else if meta.path.is_ident("flatten") {
+                    flatten = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `index = N` or `flatten`"))
+                }
+            })?;
+        }
+
+        let source = match (flatten, index) {
+            (true, index) => Source::Flatten(index.unwrap_or(0)),
+            (false, Some(index)) => Source::Index(index),
+            (false, None) => Source::Name(ident.unraw().to_string()),
+        };
+
+        Ok(RowField {
+            ident,
+            ty: &field.ty,
+            source,
+        })
+    }
+}
+
+pub struct FromRow<'a> {
+    ident: &'a Ident,
+    fields: Vec<RowField<'a>>,
+}
+
+impl<'a> FromRow<'a> {
+    /// Reads the `#[row(...)]` attributes, fields without one are read by name.
+    pub fn parse(ident: &'a Ident, fields: &'a FieldsNamed) -> Result<FromRow<'a>, syn::Error> {
+        let fields = fields.named.iter()
+            .map(|field| {
+                let name = field.ident.as_ref().ok_or_else(|| syn::Error::new(field.span(), "expected a named field"))?;
+
+                RowField::parse(field, name)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(FromRow {
+            ident,
+            fields,
+        })
+    }
+
+    /// Reads every field by its position, the layout models are selected in.
+    pub fn indexed(ident: &'a Ident, fields: &'a FieldsNamed) -> FromRow<'a> {
+        let fields = fields.named.iter()
+            .filter_map(|field| field.ident.as_ref().map(|ident| (ident, &field.ty)))
+            .enumerate()
+            .map(|(index, (ident, ty))| RowField { ident, ty, source: Source::Index(index) })
+            .collect();
+
+        FromRow {
+            ident,
+            fields,
+        }
+    }
+}
+
+impl<'a> ToTokens for FromRow<'a> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let ident = &self.ident;
+
+        let row_fields = self.fields.iter().map(|field| {
+            let name = field.ident;
+            let ty = field.ty;
+            let target = format!("{}::{}", ident, name.unraw());
+
+            match &field.source {
+                Source::Name(column) => quote! {
+                    #name: row.get(#column).map_err(|err| ::hell_orm::schema::row::field_error(row, err, #target))?
+                },
+                Source::Index(index) => quote! {
+                    #name: row.get(offset + #index).map_err(|err| ::hell_orm::schema::row::field_error(row, err, #target))?
+                },
+                Source::Flatten(index) => quote! {
+                    #name: <#ty as ::hell_orm::schema::row::FromRow>::from_row_at(row, offset + #index)?
+                },
+            }
+        });
+
+        tokens.extend(quote! {
+            impl ::hell_orm::schema::row::FromRow for #ident {
+                fn from_row_at(row: &::hell_orm::__macro_export::rusqlite::Row, offset: usize) -> ::std::result::Result<Self, ::hell_orm::__macro_export::rusqlite::Error> {
+                    Ok(Self {
+                        #(#row_fields,)*
+                    })
+                }
+            }
+        });
+    }
+}
+
@@ -0,0 +1,130 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Word(String),
+    Literal,
+    Param,
+    Symbol(&'static str),
+}
+
+impl Token {
+    /// Whether the token is the keyword `keyword`, ignoring case.
+    pub fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word(word) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    pub fn is_symbol(&self, symbol: &str) -> bool {
+        matches!(self, Token::Symbol(other) if *other == symbol)
+    }
+}
+
+const SYMBOLS: [&str; 20] = ["<=", ">=", "!=", "<>", "==", "||", "(", ")", ",", ".", "*", "=", "<", ">", "+", "-", "/", "%", ";", "|"];
+
+/// Splits a SQL statement into tokens, dropping whitespace and comments.
+pub fn tokenize(sql: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = sql;
+
+    while let Some(char) = rest.chars().next() {
+        if char.is_whitespace() {
+            rest = &rest[char.len_utf8()..];
+        } else if rest.starts_with("--") {
+            rest = rest.find('\n').map(|end| &rest[end..]).unwrap_or_default();
+        } else if rest.starts_with("/*") {
+            rest = rest.find("*/").map(|end| &rest[end + 2..]).ok_or("unterminated comment")?;
+        } else if char == '\'' {
+            let mut end = 1;
+
+            // a quote inside a string literal is escaped by doubling it
+            loop {
+                end += rest[end..].find('\'').ok_or("unterminated string literal")? + 1;
+
+                if !rest[end..].starts_with('\'') {
+                    break;
+                }
+
+                end += 1;
+            }
+
+            tokens.push(Token::Literal);
+            rest = &rest[end..];
+        } else if char == '"' || char == '`' {
+            let end = rest[1..].find(char).ok_or("unterminated quoted identifier")? + 1;
+
+            tokens.push(Token::Word(rest[1..end].to_string()));
+            rest = &rest[end + 1..];
+        } else if char == '?' {
+            if rest[1..].starts_with(|char: char| char.is_ascii_digit()) {
+                return Err(String::from("numbered placeholders are not supported, use ? instead"));
+            }
+
+            tokens.push(Token::Param);
+            rest = &rest[1..];
+        } else if char == ':' || char == '@' || char == '$' {
+            return Err(String::from("named placeholders are not supported, use ? instead"));
+        } else if char.is_ascii_digit() {
+            let end = rest.find(|char: char| !char.is_ascii_alphanumeric() && char != '.').unwrap_or(rest.len());
+
+            tokens.push(Token::Literal);
+            rest = &rest[end..];
+        } else if char.is_alphabetic() || char == '_' {
+            let end = rest.find(|char: char| !char.is_alphanumeric() && char != '_').unwrap_or(rest.len());
+
+            tokens.push(Token::Word(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) {
+            tokens.push(Token::Symbol(symbol));
+            rest = &rest[symbol.len()..];
+        } else {
+            return Err(format!("unexpected character `{}`", char));
+        }
+    }
+
+    Ok(tokens)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Token, tokenize};
+
+    fn word(word: &str) -> Token {
+        Token::Word(String::from(word))
+    }
+
+    #[test]
+    fn splits_words_symbols_and_params() {
+        let tokens = tokenize("SELECT a.b FROM t WHERE c >= ? AND d <> 1").unwrap();
+
+        assert_eq!(tokens, vec![
+            word("SELECT"), word("a"), Token::Symbol("."), word("b"), word("FROM"), word("t"), word("WHERE"),
+            word("c"), Token::Symbol(">="), Token::Param, word("AND"), word("d"), Token::Symbol("<>"), Token::Literal,
+        ]);
+    }
+
+    #[test]
+    fn skips_whitespace_and_comments() {
+        let tokens = tokenize("SELECT -- everything\n * /* from here */ FROM t").unwrap();
+
+        assert_eq!(tokens, vec![word("SELECT"), Token::Symbol("*"), word("FROM"), word("t")]);
+    }
+
+    #[test]
+    fn reads_literals_and_quoted_identifiers() {
+        let tokens = tokenize("SELECT 'it''s', 1.5e3, \"user name\", `order` FROM t").unwrap();
+
+        assert_eq!(tokens, vec![
+            word("SELECT"), Token::Literal, Token::Symbol(","), Token::Literal, Token::Symbol(","),
+            word("user name"), Token::Symbol(","), word("order"), word("FROM"), word("t"),
+        ]);
+    }
+
+    #[test]
+    fn rejects_other_placeholders_and_unterminated_input() {
+        assert!(tokenize("SELECT ?1").is_err());
+        assert!(tokenize("SELECT :name").is_err());
+        assert!(tokenize("SELECT 'open").is_err());
+        assert!(tokenize("SELECT \"open").is_err());
+        assert!(tokenize("SELECT /* open").is_err());
+        assert!(tokenize("SELECT #").is_err());
+    }
+}
@@ -0,0 +1,542 @@
+mod lexer;
+
+use lexer::{Token, tokenize};
+
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Token as SynToken, Type, LitStr, Expr, Ident};
+use quote::{quote, format_ident};
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+
+const KEYWORDS: [&str; 58] = [
+    "SELECT", "DISTINCT", "ALL", "FROM", "WHERE", "AND", "OR", "NOT", "IN", "IS", "NULL", "LIKE", "GLOB", "BETWEEN", "AS", "ON",
+    "JOIN", "INNER", "LEFT", "RIGHT", "FULL", "OUTER", "CROSS", "NATURAL", "USING", "GROUP", "BY", "HAVING", "ORDER", "ASC", "DESC",
+    "LIMIT", "OFFSET", "UNION", "INTERSECT", "EXCEPT", "EXISTS", "CASE", "WHEN", "THEN", "ELSE", "END", "COLLATE", "NOCASE",
+    "ESCAPE", "TRUE", "FALSE", "CAST", "WITH", "RECURSIVE", "VALUES", "CURRENT_TIMESTAMP", "CURRENT_DATE", "CURRENT_TIME",
+    "NULLS", "FIRST", "LAST", "FILTER",
+];
+
+const COMPARISONS: [&str; 8] = ["=", "==", "!=", "<>", "<", ">", "<=", ">="];
+
+fn is_keyword(word: &str) -> bool {
+    KEYWORDS.iter().any(|keyword| keyword.eq_ignore_ascii_case(word))
+}
+
+fn is_comparison(token: &Token) -> bool {
+    COMPARISONS.iter().any(|symbol| token.is_symbol(symbol)) || token.is_keyword("LIKE") || token.is_keyword("GLOB")
+}
+
+/// Whether `token` can end a selected value, so that a word following it is an alias.
+fn ends_value(token: &Token) -> bool {
+    match token {
+        Token::Word(word) => !is_keyword(word) || ["END", "NULL", "TRUE", "FALSE"].iter().any(|keyword| keyword.eq_ignore_ascii_case(word)),
+        Token::Literal | Token::Param => true,
+        Token::Symbol(symbol) => *symbol == ")",
+    }
+}
+
+/// The index of the parenthesis closing the one at `open`.
+fn closing_paren(tokens: &[Token], open: usize) -> Option<usize> {
+    let mut depth = 0;
+
+    for (index, token) in tokens.iter().enumerate().skip(open) {
+        if token.is_symbol("(") {
+            depth += 1;
+        } else if token.is_symbol(")") {
+            depth -= 1;
+
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+    }
+
+    None
+}
+
+/// The typed column constant for `column` on `model`, the hidden `deleted_at` and `rowid` columns included.
+fn column_path(model: &Ident, column: &ColumnRef, span: proc_macro2::Span) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let name = column.name.to_lowercase();
+
+    if name == "deleted_at" {
+        return Ok(quote! { ::hell_orm::schema::column::Column::<#model, ::std::option::Option<i64>>::new("deleted_at") });
+    }
+
+    if ["rowid", "oid", "_rowid_"].contains(&name.as_str()) {
+        return Ok(quote! { ::hell_orm::schema::column::Column::<#model, i64>::new("rowid") });
+    }
+
+    let ident = syn::parse_str::<Ident>(&format!("{}_COL", column.name.to_uppercase()))
+        .map_err(|_| syn::Error::new(span, format!("`{}` is not a column of any model", column.name)))?;
+
+    let ident = Ident::new(&ident.to_string(), span);
+
+    Ok(quote! { <#model>::#ident })
+}
+
+/// A column of one of the tables in the statement.
+#[derive(Clone)]
+struct ColumnRef {
+    table: usize,
+    name: String,
+}
+
+/// An item of the select list, the tokens of its value and the index of its alias if it has one.
+struct SelectItem {
+    value: Range<usize>,
+    alias: Option<usize>,
+}
+
+/// A value in the select list, decoding into one element of the output row.
+enum Output {
+    Column(ColumnRef),
+    Model(usize),
+    Count,
+}
+
+pub struct QueryAs {
+    schema: Type,
+    sql: LitStr,
+    params: Vec<Expr>,
+}
+
+impl Parse for QueryAs {
+    fn parse(input: ParseStream) -> syn::Result<QueryAs> {
+        let schema = input.parse()?;
+        input.parse::<SynToken![,]>()?;
+
+        let sql = input.parse()?;
+
+        let params = if input.is_empty() {
+            Vec::new()
+        } else {
+            input.parse::<SynToken![,]>()?;
+
+            Punctuated::<Expr, SynToken![,]>::parse_terminated(input)?.into_iter().collect()
+        };
+
+        Ok(QueryAs {
+            schema,
+            sql,
+            params,
+        })
+    }
+}
+
+/// The tables, columns and placeholders found in a statement.
+struct Analysis {
+    tables: Vec<String>,
+    columns: HashMap<usize, (usize, ColumnRef)>,
+    outputs: Vec<Output>,
+    params: Vec<Option<ColumnRef>>,
+}
+
+impl Analysis {
+    fn new(tokens: &[Token]) -> Result<Analysis, String> {
+        let items = Analysis::select_items(tokens)?;
+
+        let mut tables = Vec::<String>::new();
+        let mut aliases = HashMap::new();
+        let mut names = HashSet::new();
+
+        // bare select aliases such as `SELECT name n` name a result, not a column
+        for alias in items.iter().filter_map(|item| item.alias) {
+            if let Token::Word(name) = &tokens[alias] {
+                names.insert(name.to_lowercase());
+            }
+        }
+
+        for (index, token) in tokens.iter().enumerate() {
+            if token.is_keyword("AS") && let Some(Token::Word(name)) = tokens.get(index + 1) {
+                names.insert(name.to_lowercase());
+            }
+
+            if !token.is_keyword("FROM") && !token.is_keyword("JOIN") {
+                continue;
+            }
+
+            let mut index = index + 1;
+
+            loop {
+                let Some(Token::Word(table)) = tokens.get(index) else {
+                    return Err(String::from("expected a table name, subqueries in FROM are not supported"));
+                };
+
+                let table = table.to_lowercase();
+
+                let position = tables.iter().position(|other| *other == table).unwrap_or_else(|| {
+                    tables.push(table.clone());
+                    tables.len() - 1
+                });
+
+                aliases.insert(table, position);
+
+                index += 1;
+
+                if tokens.get(index).is_some_and(|token| token.is_keyword("AS")) {
+                    index += 1;
+                }
+
+                if let Some(Token::Word(alias)) = tokens.get(index) && !is_keyword(alias) {
+                    aliases.insert(alias.to_lowercase(), position);
+                    index += 1;
+                }
+
+                if !token.is_keyword("FROM") || !tokens.get(index).is_some_and(|token| token.is_symbol(",")) {
+                    break;
+                }
+
+                index += 1;
+            }
+        }
+
+        if tables.is_empty() {
+            return Err(String::from("expected a FROM clause"));
+        }
+
+        // column references, keyed by the index of their first token
+        let mut columns = HashMap::new();
+
+        for (index, token) in tokens.iter().enumerate() {
+            let Token::Word(word) = token else {
+                continue;
+            };
+
+            let previous = index.checked_sub(1).and_then(|index| tokens.get(index));
+            let next = tokens.get(index + 1);
+
+            if previous.is_some_and(|token| token.is_symbol(".") || token.is_keyword("AS")) {
+                continue;
+            }
+
+            if next.is_some_and(|token| token.is_symbol(".")) {
+                if let (Some(table), Some(Token::Word(name))) = (aliases.get(&word.to_lowercase()), tokens.get(index + 2)) {
+                    columns.insert(index, (index + 2, ColumnRef { table: *table, name: name.clone() }));
+                } else if !tokens.get(index + 2).is_some_and(|token| token.is_symbol("*")) {
+                    return Err(format!("unknown table `{}`", word));
+                }
+            } else if !next.is_some_and(|token| token.is_symbol("(")) && !is_keyword(word) && !aliases.contains_key(&word.to_lowercase()) && !names.contains(&word.to_lowercase()) {
+                if tables.len() > 1 {
+                    return Err(format!("the column `{}` must be qualified with its table when selecting from several tables", word));
+                }
+
+                columns.insert(index, (index, ColumnRef { table: 0, name: word.clone() }));
+            }
+        }
+
+        let outputs = Analysis::outputs(tokens, &items, &tables, &aliases, &columns)?;
+        let params = Analysis::params(tokens, &columns);
+
+        Ok(Analysis {
+            tables,
+            columns,
+            outputs,
+            params,
+        })
+    }
+
+    /// Splits the select list into its items, separating an `AS name` or a bare alias from the value.
+    fn select_items(tokens: &[Token]) -> Result<Vec<SelectItem>, String> {
+        if !tokens.first().is_some_and(|token| token.is_keyword("SELECT")) {
+            return Err(String::from("query_as! only supports SELECT statements"));
+        }
+
+        let mut start = 1;
+
+        if tokens.get(start).is_some_and(|token| token.is_keyword("DISTINCT") || token.is_keyword("ALL")) {
+            start += 1;
+        }
+
+        let mut depth = 0;
+        let mut bounds = vec![start];
+        let mut end = start;
+
+        while let Some(token) = tokens.get(end) {
+            match token {
+                Token::Symbol("(") => depth += 1,
+                Token::Symbol(")") => depth -= 1,
+                Token::Symbol(",") if depth == 0 => bounds.push(end + 1),
+                token if depth == 0 && token.is_keyword("FROM") => break,
+                _ => {},
+            }
+
+            end += 1;
+        }
+
+        bounds.push(end + 1);
+
+        let items = bounds.windows(2)
+            .map(|window| {
+                let (start, end) = (window[0], window[1] - 1);
+
+                let alias = match &tokens[start..end] {
+                    [.., as_, Token::Word(_)] if as_.is_keyword("AS") => Some(end - 1),
+                    [.., previous, Token::Word(alias)] if !is_keyword(alias) && ends_value(previous) => Some(end - 1),
+                    _ => None,
+                };
+
+                let value = match alias {
+                    Some(alias) if tokens[alias - 1].is_keyword("AS") => start..alias - 1,
+                    Some(alias) => start..alias,
+                    None => start..end,
+                };
+
+                SelectItem {
+                    value,
+                    alias,
+                }
+            })
+            .collect();
+
+        Ok(items)
+    }
+
+    fn outputs(tokens: &[Token], items: &[SelectItem], tables: &[String], aliases: &HashMap<String, usize>, columns: &HashMap<usize, (usize, ColumnRef)>) -> Result<Vec<Output>, String> {
+        items.iter()
+            .map(|item| {
+                let value = &tokens[item.value.clone()];
+
+                match value {
+                    [Token::Symbol("*")] if tables.len() == 1 => Ok(Output::Model(0)),
+                    [Token::Symbol("*")] => Err(String::from("`*` is ambiguous when selecting from several tables, use table.* instead")),
+                    [Token::Word(table), Token::Symbol("."), Token::Symbol("*")] => aliases.get(&table.to_lowercase())
+                        .map(|table| Output::Model(*table))
+                        .ok_or_else(|| format!("unknown table `{}`", table)),
+                    [Token::Word(count), Token::Symbol("("), ..] if count.eq_ignore_ascii_case("COUNT") && closing_paren(value, 1) == Some(value.len() - 1) => Ok(Output::Count),
+                    _ => {
+                        let index = item.value.start;
+
+                        columns.get(&index)
+                            .filter(|(end, _)| *end + 1 == item.value.end)
+                            .map(|(_, column)| Output::Column(column.clone()))
+                            .ok_or_else(|| String::from("cannot infer the type of a selected expression, select columns, table.* or COUNT(...)"))
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// The column each placeholder is compared with, if any.
+    fn params(tokens: &[Token], columns: &HashMap<usize, (usize, ColumnRef)>) -> Vec<Option<ColumnRef>> {
+        let ending = |end: usize| columns.values().find(|(other, _)| *other == end).map(|(_, column)| column.clone());
+
+        // the column right before `index`, skipping a `NOT`
+        let before = |index: usize| {
+            let mut end = index.checked_sub(1)?;
+
+            if tokens[end].is_keyword("NOT") {
+                end = end.checked_sub(1)?;
+            }
+
+            ending(end)
+        };
+
+        tokens.iter()
+            .enumerate()
+            .filter(|(_, token)| **token == Token::Param)
+            .map(|(index, _)| {
+                let previous = index.checked_sub(1).map(|index| &tokens[index]);
+
+                if let Some(previous) = previous && is_comparison(previous) {
+                    return before(index - 1);
+                }
+
+                if previous.is_some_and(|token| token.is_keyword("BETWEEN")) {
+                    return before(index - 1);
+                }
+
+                if previous.is_some_and(|token| token.is_keyword("AND")) && index >= 3 && tokens[index - 2] == Token::Param && tokens[index - 3].is_keyword("BETWEEN") {
+                    return before(index - 3);
+                }
+
+                let mut start = index;
+
+                while start > 0 && (tokens[start - 1] == Token::Param || tokens[start - 1].is_symbol(",")) {
+                    start -= 1;
+                }
+
+                if start >= 2 && tokens[start - 1].is_symbol("(") && tokens[start - 2].is_keyword("IN") {
+                    return before(start - 2);
+                }
+
+                if tokens.get(index + 1).is_some_and(is_comparison) {
+                    return columns.get(&(index + 2)).map(|(_, column)| column.clone());
+                }
+
+                None
+            })
+            .collect()
+    }
+}
+
+impl QueryAs {
+    pub fn expand(&self) -> Result<proc_macro2::TokenStream, syn::Error> {
+        let span = self.sql.span();
+        let sql = self.sql.value();
+        let schema = &self.schema;
+
+        let tokens = tokenize(&sql).map_err(|err| syn::Error::new(span, err))?;
+        let analysis = Analysis::new(&tokens).map_err(|err| syn::Error::new(span, err))?;
+
+        if analysis.params.len() != self.params.len() {
+            return Err(syn::Error::new(span, format!("expected {} parameters, found {}", analysis.params.len(), self.params.len())));
+        }
+
+        let models = (0..analysis.tables.len()).map(|index| format_ident!("__Table{}", index)).collect::<Vec<Ident>>();
+
+        let column = |column: &ColumnRef| column_path(&models[column.table], column, span);
+
+        let tables = analysis.tables.iter().zip(models.iter()).map(|(table, model)| quote! {
+            type #model = <#schema as ::hell_orm::schema::table::Table<{ ::hell_orm::schema::table::table_hash(#table) }>>::Model;
+        });
+
+        let checks = analysis.columns.values()
+            .map(|(_, reference)| column(reference))
+            .collect::<Result<Vec<_>, syn::Error>>()?;
+
+        let binds = analysis.params.iter().zip(self.params.iter())
+            .map(|(reference, param)| match reference {
+                Some(reference) => {
+                    let column = column(reference)?;
+
+                    Ok(quote! { ::hell_orm::schema::checked::bind(#column, #param) })
+                },
+                None => Ok(quote! { ::hell_orm::schema::checked::bind_any(#param) }),
+            })
+            .collect::<Result<Vec<_>, syn::Error>>()?;
+
+        let outputs = analysis.outputs.iter()
+            .map(|output| match output {
+                Output::Column(reference) => {
+                    let column = column(reference)?;
+
+                    Ok(quote! { ::hell_orm::schema::checked::decode(#column, row, &mut __offset)? })
+                },
+                Output::Model(table) => {
+                    let model = &models[*table];
+
+                    Ok(quote! { ::hell_orm::schema::checked::decode_model::<#model>(row, &mut __offset)? })
+                },
+                Output::Count => Ok(quote! { ::hell_orm::schema::checked::decode_count(row, &mut __offset)? }),
+            })
+            .collect::<Result<Vec<_>, syn::Error>>()?;
+
+        let values = (0..outputs.len()).map(|index| format_ident!("__value{}", index)).collect::<Vec<Ident>>();
+
+        let output = match values.as_slice() {
+            [value] => quote! { #value },
+            values => quote! { (#(#values,)*) },
+        };
+
+        Ok(quote! {
+            {
+                #(#tables)*
+
+                const _: () = {
+                    #(let _ = ::std::marker::PhantomData::<#models>;)*
+                    #(let _ = #checks;)*
+                };
+
+                ::hell_orm::schema::checked::CheckedQuery::<#schema, _>::new(#sql, ::std::vec![#(#binds),*], |row| {
+                    let mut __offset = 0;
+
+                    #(let #values = #outputs;)*
+
+                    Ok(#output)
+                })
+            }
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Analysis, Output, QueryAs, tokenize};
+
+    fn analyse(sql: &str) -> Result<Analysis, String> {
+        Analysis::new(&tokenize(sql).unwrap())
+    }
+
+    fn column_names(analysis: &Analysis) -> Vec<String> {
+        let mut names = analysis.columns.values().map(|(_, column)| column.name.clone()).collect::<Vec<String>>();
+
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn finds_tables_and_aliases() {
+        let analysis = analyse("SELECT u.name, p.* FROM users u INNER JOIN posts AS p ON p.user = u.id").unwrap();
+
+        assert_eq!(analysis.tables, vec!["users", "posts"]);
+        assert!(matches!(analysis.outputs.as_slice(), [Output::Column(column), Output::Model(1)] if column.table == 0 && column.name == "name"));
+        assert_eq!(column_names(&analysis), vec!["id", "name", "user"]);
+    }
+
+    #[test]
+    fn infers_outputs() {
+        let analysis = analyse("SELECT *, COUNT(DISTINCT name) FROM users").unwrap();
+
+        assert!(matches!(analysis.outputs.as_slice(), [Output::Model(0), Output::Count]));
+    }
+
+    #[test]
+    fn only_counts_a_whole_count() {
+        assert!(analyse("SELECT COUNT(*) * 1.5 FROM users").is_err());
+        assert!(analyse("SELECT COUNT(*) + COUNT(id) FROM users").is_err());
+        assert!(analyse("SELECT COUNT(*) total FROM users").is_ok());
+    }
+
+    #[test]
+    fn aliases_are_not_columns() {
+        let analysis = analyse("SELECT name n, COUNT(*) AS total FROM users ORDER BY n, total").unwrap();
+
+        assert_eq!(column_names(&analysis), vec!["name"]);
+        assert!(matches!(analysis.outputs.as_slice(), [Output::Column(_), Output::Count]));
+    }
+
+    #[test]
+    fn expressions_are_not_aliases() {
+        assert!(analyse("SELECT id + name FROM users").is_err());
+    }
+
+    #[test]
+    fn reads_rowid() {
+        let analysis = analyse("SELECT rowid FROM users WHERE rowid > ?").unwrap();
+
+        assert!(matches!(analysis.outputs.as_slice(), [Output::Column(column)] if column.name == "rowid"));
+        assert!(matches!(analysis.params.as_slice(), [Some(column)] if column.name == "rowid"));
+    }
+
+    #[test]
+    fn matches_params_with_columns() {
+        let analysis = analyse("SELECT * FROM users WHERE id = ? AND ? < age AND name NOT IN (?, ?) AND age BETWEEN ? AND ? LIMIT ?").unwrap();
+
+        let params = analysis.params.iter()
+            .map(|column| column.as_ref().map(|column| column.name.as_str()))
+            .collect::<Vec<Option<&str>>>();
+
+        assert_eq!(params, vec![Some("id"), Some("age"), Some("name"), Some("name"), Some("age"), Some("age"), None]);
+    }
+
+    #[test]
+    fn rejects_unsupported_statements() {
+        assert!(analyse("DELETE FROM users").is_err());
+        assert!(analyse("SELECT 1").is_err());
+        assert!(analyse("SELECT * FROM (SELECT * FROM users)").is_err());
+        assert!(analyse("SELECT * FROM users, posts").is_err());
+        assert!(analyse("SELECT name FROM users INNER JOIN posts ON posts.user = users.id").is_err());
+        assert!(analyse("SELECT x.name FROM users").is_err());
+    }
+
+    #[test]
+    fn reports_invalid_column_names() {
+        let query = syn::parse_str::<QueryAs>(r#"Schema, "SELECT \"user name\" FROM users""#).unwrap();
+
+        assert!(query.expand().is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use crate::insert::field::ModelField;
+
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+use syn::{Token, Ident, Field};
+use quote::{quote, format_ident, ToTokens};
+
+
+pub struct UpdateStructFields<'a> {
+    fields: Vec<ModelField<'a>>,
+}
+
+impl<'a> UpdateStructFields<'a> {
+    pub fn new(fields: &'a Punctuated<Field, Token![,]>) -> UpdateStructFields<'a> {
+        UpdateStructFields {
+            fields: fields.iter().map(ModelField::new).collect(),
+        }
+    }
+}
+
+impl<'a> ToTokens for UpdateStructFields<'a> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        for field in self.fields.iter() {
+            let ident = field.ident();
+            let type_ = field.type_();
+
+            tokens.extend(quote! { #ident: ::std::option::Option<#type_>, });
+        }
+    }
+}
+
+pub struct UpdateStructFunctions<'a> {
+    fields: Vec<ModelField<'a>>,
+    update_ident: &'a Ident,
+}
+
+impl<'a> UpdateStructFunctions<'a> {
+    pub fn new(fields: &'a Punctuated<Field, Token![,]>, update_ident: &'a Ident) -> UpdateStructFunctions<'a> {
+        UpdateStructFunctions {
+            fields: fields.iter().map(ModelField::new).collect(),
+            update_ident,
+        }
+    }
+}
+
+impl<'a> ToTokens for UpdateStructFunctions<'a> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let update_ident = &self.update_ident;
+
+        for field in self.fields.iter() {
+            let ident = field.ident();
+            let type_ = field.type_();
+
+            // the version column is bumped by the update itself, its value is only ever the expected one
+            if field.has_attribute("version") {
+                tokens.extend(quote! {
+                    /// Only updates rows still at `version`, `finish` fails with `Error::StaleVersion` when none is.
+                    pub fn expect_version(mut self, version: #type_) -> #update_ident<'a, S> {
+                        self.#ident = Some(version);
+                        self
+                    }
+                });
+
+                continue;
+            }
+
+            tokens.extend(quote! {
+                pub fn #ident(mut self, #ident: #type_) -> #update_ident<'a, S> {
+                    self.#ident = Some(#ident);
+                    self
+                }
+            });
+        }
+    }
+}
+
+pub struct UpdateFinishParams<'a> {
+    fields: Vec<ModelField<'a>>,
+}
+
+impl<'a> UpdateFinishParams<'a> {
+    pub fn new(fields: &'a Punctuated<Field, Token![,]>) -> UpdateFinishParams<'a> {
+        UpdateFinishParams {
+            fields: fields.iter().map(ModelField::new).collect(),
+        }
+    }
+}
+
+impl<'a> ToTokens for UpdateFinishParams<'a> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        for field in self.fields.iter() {
+            let ident = field.ident();
+            let type_ = field.type_();
+            let column = field.ident().as_ref().map(|ident| ident.unraw().to_string());
+
+            if field.has_attribute("updated_at") {
+                let value = format_ident!("__{}", ident.as_ref().expect("expected a named field"));
+
+                tokens.extend(quote! {
+                    let #value = self.#ident.unwrap_or_else(<#type_ as ::hell_orm::schema::timestamp::Timestamp>::now);
+                    columns.push(#column);
+                    params.push(&#value as &dyn ::hell_orm::__macro_export::rusqlite::ToSql);
+                });
+            } else if field.has_attribute("version") {
+                tokens.extend(quote! {
+                    let builder = match self.#ident {
+                        Some(version) => builder.expect_version(version),
+                        None => builder,
+                    };
+                });
+            } else {
+                tokens.extend(quote! {
+                    if let Some(value) = &self.#ident {
+                        columns.push(#column);
+                        params.push(value as &dyn ::hell_orm::__macro_export::rusqlite::ToSql);
+                    }
+                });
+            }
+        }
+    }
+}
+
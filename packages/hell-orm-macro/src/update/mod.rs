@@ -0,0 +1,67 @@
+mod builder;
+
+use crate::insert::builder::BuilderStructInit;
+
+use builder::{UpdateStructFields, UpdateStructFunctions, UpdateFinishParams};
+
+use syn::{DeriveInput, FieldsNamed};
+use quote::{quote, format_ident, ToTokens};
+
+
+pub struct Update<'a> {
+    input: &'a DeriveInput,
+    fields: &'a FieldsNamed,
+}
+
+impl<'a> Update<'a> {
+    pub fn new(input: &'a DeriveInput, fields: &'a FieldsNamed) -> Update<'a> {
+        Update {
+            input,
+            fields,
+        }
+    }
+}
+
+impl<'a> ToTokens for Update<'a> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let update_ident = format_ident!("__{}UpdateBuilder", self.input.ident);
+        let query_ident = format_ident!("__{}QueryBuilder", self.input.ident);
+        let ident = &self.input.ident;
+
+        let update_struct_init = BuilderStructInit::new(&self.fields.named);
+        let update_struct_fields = UpdateStructFields::new(&self.fields.named);
+        let update_struct_functions = UpdateStructFunctions::new(&self.fields.named, &update_ident);
+        let update_finish_params = UpdateFinishParams::new(&self.fields.named);
+
+        tokens.extend(quote! {
+            pub struct #update_ident<'a, S> {
+                builder: ::hell_orm::schema::update::UpdateBuilder<'a, #ident, S>,
+
+                #update_struct_fields
+            }
+
+            impl<'a, S> #update_ident<'a, S> {
+                #update_struct_functions
+
+                pub fn finish(self) -> ::std::result::Result<usize, ::hell_orm::error::Error> {
+                    let mut columns: ::std::vec::Vec<&str> = ::std::vec::Vec::new();
+                    let mut params: ::std::vec::Vec<&dyn ::hell_orm::__macro_export::rusqlite::ToSql> = ::std::vec::Vec::new();
+                    let builder = self.builder;
+                    #update_finish_params
+                    builder.finish(columns.as_slice(), params.as_slice())
+                }
+            }
+
+            impl<'a, S> #query_ident<'a, S> {
+                pub fn update(self) -> #update_ident<'a, S> {
+                    #update_ident {
+                        builder: self.builder.update(),
+
+                        #update_struct_init
+                    }
+                }
+            }
+        });
+    }
+}
+
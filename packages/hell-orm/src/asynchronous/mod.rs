@@ -0,0 +1,169 @@
+//! An async handle to a database, available with the `tokio` feature.
+
+use crate::schema::insert::Insert;
+use crate::schema::query::Query;
+use crate::schema::{Model, Schema, SchemaHas};
+use crate::options::DatabaseConfig;
+use crate::error::Error;
+use crate::Database;
+
+use tokio::sync::oneshot;
+
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+
+
+type Job<T> = Box<dyn FnOnce(&mut Database<T>) + Send>;
+
+/// Runs every operation on a dedicated thread owning the connection, so awaiting never blocks the executor.
+///
+/// Operations run one at a time in the order they were sent. Clones share the same thread,
+/// which exits once the last clone is dropped. A panicking operation fails with `Error::ThreadError`
+/// and rolls back its open transaction, while the thread keeps serving the others.
+pub struct AsyncDatabase<T: Schema> {
+    jobs: mpsc::Sender<Job<T>>,
+}
+
+impl<T: Schema> Clone for AsyncDatabase<T> {
+    fn clone(&self) -> AsyncDatabase<T> {
+        AsyncDatabase {
+            jobs: self.jobs.clone(),
+        }
+    }
+}
+
+impl<T: Schema + Send + 'static> AsyncDatabase<T> {
+    /// Opens the database on the worker thread, see `Database::open`.
+    pub async fn open(path: impl AsRef<Path>, config: DatabaseConfig) -> Result<AsyncDatabase<T>, Error> {
+        let path: PathBuf = path.as_ref().to_path_buf();
+        let (sender, receiver) = oneshot::channel();
+
+        thread::spawn(move || {
+            match Database::open(path, config) {
+                Ok(database) => {
+                    let (jobs, queue) = mpsc::channel();
+
+                    if sender.send(Ok(AsyncDatabase { jobs })).is_ok() {
+                        AsyncDatabase::work(database, queue);
+                    }
+                },
+                Err(err) => {
+                    let _ = sender.send(Err(err));
+                },
+            }
+        });
+
+        receiver.await.map_err(|_| stopped())?
+    }
+
+    /// Moves an already open database onto its own worker thread.
+    pub fn new(database: Database<T>) -> AsyncDatabase<T> {
+        let (jobs, queue) = mpsc::channel();
+
+        thread::spawn(move || AsyncDatabase::work(database, queue));
+
+        AsyncDatabase {
+            jobs,
+        }
+    }
+
+    fn work(mut database: Database<T>, queue: mpsc::Receiver<Job<T>>) {
+        for job in queue {
+            job(&mut database);
+        }
+    }
+
+    /// Runs `f` with exclusive access to the database and returns its result.
+    pub async fn run<R, F>(&self, f: F) -> Result<R, Error>
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut Database<T>) -> Result<R, Error> + Send + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+
+        self.jobs.send(Box::new(move |database| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| f(&mut *database))).unwrap_or_else(|payload| {
+                // don't leave a transaction open for the operations that follow
+                if !database.with_connection(|connection| connection.is_autocommit()) {
+                    let _ = database.execute_raw("ROLLBACK", []);
+                }
+
+                Err(Error::ThreadError(panic_message(payload).into()))
+            });
+
+            let _ = sender.send(result);
+        }))
+        .map_err(|_| stopped())?;
+
+        receiver.await.map_err(|_| stopped())?
+    }
+
+    /// Builds and finishes an insert on the worker thread.
+    pub async fn insert<Row, R, F>(&self, f: F) -> Result<R, Error>
+    where
+        Row: Model,
+        T: SchemaHas<Row>,
+        R: Send + 'static,
+        F: for<'a> FnOnce(<Row as Insert>::Builder<'a>) -> Result<R, Error> + Send + 'static,
+    {
+        self.run(move |database| f(database.insert::<Row>())).await
+    }
+
+    /// Builds and runs a query on the worker thread.
+    pub async fn query<Row, R, F>(&self, f: F) -> Result<R, Error>
+    where
+        Row: Model,
+        T: SchemaHas<Row>,
+        R: Send + 'static,
+        F: for<'a> FnOnce(<Row as Query>::Builder<'a, T>) -> Result<R, Error> + Send + 'static,
+    {
+        self.run(move |database| f(database.query::<Row>())).await
+    }
+
+    /// Filters the rows and finishes the update started with `.update()`, returning the changed row count.
+    ///
+    /// Mirrors `db.query::<Row>().filter(..).update().name(..).finish()` on a `Database`.
+    pub async fn update<Row, F>(&self, f: F) -> Result<usize, Error>
+    where
+        Row: Model,
+        T: SchemaHas<Row>,
+        F: for<'a> FnOnce(<Row as Query>::Builder<'a, T>) -> Result<usize, Error> + Send + 'static,
+    {
+        self.query::<Row, _, _>(f).await
+    }
+
+    /// Filters the rows and calls `.delete()`, returning the deleted row count.
+    ///
+    /// Mirrors `db.query::<Row>().filter(..).delete()` on a `Database`.
+    pub async fn delete<Row, F>(&self, f: F) -> Result<usize, Error>
+    where
+        Row: Model,
+        T: SchemaHas<Row>,
+        F: for<'a> FnOnce(<Row as Query>::Builder<'a, T>) -> Result<usize, Error> + Send + 'static,
+    {
+        self.query::<Row, _, _>(f).await
+    }
+
+    /// Runs `f` inside a transaction on the worker thread, see `Database::transaction`.
+    pub async fn transaction<R, F>(&self, f: F) -> Result<R, Error>
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut Database<T>) -> Result<R, Error> + Send + 'static,
+    {
+        self.run(move |database| database.transaction(f)).await
+    }
+}
+
+fn stopped() -> Error {
+    Error::ThreadError("the database thread has stopped".into())
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().map(|message| message.to_string()).unwrap_or_else(|| String::from("the operation panicked")),
+    }
+}
+
@@ -4,16 +4,46 @@
 #[derive(Debug)]
 pub enum Error {
     /// An error occurred while opening the database file.
-    OpenError(Box<dyn std::error::Error>),
+    OpenError(Box<dyn std::error::Error + Send + Sync>),
 
     /// An error occurred while creating or modifying the database schema.
-    SchemaError(Box<dyn std::error::Error>),
+    SchemaError(Box<dyn std::error::Error + Send + Sync>),
 
     /// An error occurred while preparing a statement.
-    StatementError(Box<dyn std::error::Error>),
+    StatementError(Box<dyn std::error::Error + Send + Sync>),
 
     /// An error occurred while executing an insert.
-    InsertError(Box<dyn std::error::Error>),
+    InsertError(Box<dyn std::error::Error + Send + Sync>),
+
+    /// An error occurred while executing a query.
+    QueryError(Box<dyn std::error::Error + Send + Sync>),
+
+    /// An error occurred while executing an update.
+    UpdateError(Box<dyn std::error::Error + Send + Sync>),
+
+    /// An error occurred while executing a delete.
+    DeleteError(Box<dyn std::error::Error + Send + Sync>),
+
+    /// An error occurred while executing a raw statement.
+    ExecuteError(Box<dyn std::error::Error + Send + Sync>),
+
+    /// An update expected a `#[version]` that no longer matches any row.
+    StaleVersion,
+
+    /// A pagination cursor could not be decoded or does not match the sort order of the query.
+    InvalidCursor,
+
+    /// A paginated query sorts in mixed directions, or a row's sort key is NULL and can't be a cursor.
+    InvalidPageOrder,
+
+    /// No pooled connection became idle before the timeout.
+    PoolTimeout,
+
+    /// The calling thread asked a pool for a connection while already holding every connection of that kind.
+    PoolReentered,
+
+    /// An operation on the thread of an `AsyncDatabase` panicked, or the thread has stopped.
+    ThreadError(Box<dyn std::error::Error + Send + Sync>),
 }
 
 impl std::fmt::Display for Error {
@@ -23,6 +53,16 @@ impl std::fmt::Display for Error {
             Error::SchemaError(error) => f.write_fmt(format_args!("failed to create schema: {}", error)),
             Error::StatementError(error) => f.write_fmt(format_args!("failed to prepare statement: {}", error)),
             Error::InsertError(error) => f.write_fmt(format_args!("failed to insert: {}", error)),
+            Error::QueryError(error) => f.write_fmt(format_args!("failed to query: {}", error)),
+            Error::UpdateError(error) => f.write_fmt(format_args!("failed to update: {}", error)),
+            Error::DeleteError(error) => f.write_fmt(format_args!("failed to delete: {}", error)),
+            Error::ExecuteError(error) => f.write_fmt(format_args!("failed to execute: {}", error)),
+            Error::StaleVersion => f.write_str("failed to update: the row was modified by someone else"),
+            Error::InvalidCursor => f.write_str("failed to paginate: invalid cursor"),
+            Error::InvalidPageOrder => f.write_str("failed to paginate: sort keys must share one direction and never be NULL"),
+            Error::PoolTimeout => f.write_str("failed to get a pooled connection: timed out"),
+            Error::PoolReentered => f.write_str("failed to get a pooled connection: this thread already holds every connection"),
+            Error::ThreadError(error) => f.write_fmt(format_args!("database thread failed: {}", error)),
         }
     }
 }
@@ -1,12 +1,20 @@
 pub mod schema;
 pub mod error;
+pub mod options;
+pub mod pool;
+#[cfg(feature = "tokio")]
+pub mod asynchronous;
 
-use crate::schema::insert::Insert;
-use crate::schema::query::Query;
-use crate::schema::{Model, Schema, SchemaHas};
+use crate::schema::insert::{Insert, InsertBuilder};
+use crate::schema::query::{Query, QueryBuilder, select_list};
+use crate::schema::column::Column;
+use crate::schema::relation::BelongsTo;
+use crate::schema::row::FromRow;
+use crate::schema::{Model, PrimaryKey, Schema, SchemaHas};
+use crate::options::{OpenOptions, OpenFlags, DatabaseConfig};
 use crate::error::Error;
 
-use rusqlite::Connection;
+use rusqlite::{Connection, Params, ToSql};
 
 use std::path::Path;
 use std::marker::PhantomData;
@@ -17,10 +25,56 @@ pub struct Database<T: Schema> {
 }
 
 impl<T: Schema> Database<T> {
-    pub fn open(path: impl AsRef<Path>) -> Result<Database<T>, Error> {
-        let mut connection = Connection::open(path).map_err(|err| Error::OpenError(Box::new(err)))?;
+    /// Opens or creates the database file, applying `config` before the schema is created.
+    pub fn open(path: impl AsRef<Path>, config: DatabaseConfig) -> Result<Database<T>, Error> {
+        Database::open_configured(path, &OpenOptions::default(), &config)
+    }
+
+    /// Opens a private in-memory database, which is dropped with the connection.
+    pub fn open_in_memory() -> Result<Database<T>, Error> {
+        Database::open_in_memory_with(DatabaseConfig::new())
+    }
+
+    /// Opens a private in-memory database applying `config`, see `open_in_memory`.
+    pub fn open_in_memory_with(config: DatabaseConfig) -> Result<Database<T>, Error> {
+        Database::open(":memory:", config)
+    }
+
+    /// Opens an existing database without write access, checking that every table of the schema is present.
+    pub fn open_read_only(path: impl AsRef<Path>) -> Result<Database<T>, Error> {
+        let options = OpenOptions {
+            flags: OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            ..OpenOptions::default()
+        };
+
+        Database::open_with(path, options)
+    }
+
+    /// Opens the database with explicit SQLite flags, VFS and pragmas, see `OpenOptions`.
+    ///
+    /// The file is opened through `options.vfs` when set, or the default VFS otherwise, and the
+    /// pragmas run before the schema is touched.
+    /// With `SQLITE_OPEN_READ_ONLY` in the flags the schema is verified instead of created, so
+    /// opening fails if any table is missing rather than writing to the file.
+    pub fn open_with(path: impl AsRef<Path>, options: OpenOptions) -> Result<Database<T>, Error> {
+        Database::open_configured(path, &options, &DatabaseConfig::new())
+    }
+
+    /// Opens with `options`, then applies `config` after its pragmas.
+    pub(crate) fn open_configured(path: impl AsRef<Path>, options: &OpenOptions, config: &DatabaseConfig) -> Result<Database<T>, Error> {
+        let connection = options.open(path)?;
+
+        config.apply(&connection)?;
+
+        Database::with_schema(connection, options.is_read_only())
+    }
 
-        T::create(&mut connection)?;
+    fn with_schema(mut connection: Connection, read_only: bool) -> Result<Database<T>, Error> {
+        if read_only {
+            T::verify(&connection)?;
+        } else {
+            T::create(&mut connection)?;
+        }
 
         Ok(Database {
             connection,
@@ -35,20 +89,165 @@ impl<T: Schema> Database<T> {
         <Row as Insert>::builder(&mut self.connection)
     }
 
-    /*
-    pub fn query<Row: Model>(&self)
+    pub fn query<'a, Row: Model>(&'a mut self) -> <Row as Query>::Builder<'a, T>
     where
         T: SchemaHas<Row>
     {
+        <Row as Query>::builder(&mut self.connection)
+    }
+
+    /// Sets how many prepared statements are kept for reuse, `0` disables caching.
+    ///
+    /// Inserts, queries, updates and deletes prepare their SQL through the cache, so repeating
+    /// the same builder calls skips parsing the statement again.
+    /// Queries filtering with `in_` are prepared on their own instead, since their SQL changes with
+    /// the length of the list.
+    pub fn set_statement_cache_capacity(&mut self, capacity: usize) {
+        self.connection.set_prepared_statement_cache_capacity(capacity);
+    }
+
+    /// Executes a raw statement, returning the number of changed rows.
+    pub fn execute_raw(&mut self, sql: &str, params: impl Params) -> Result<usize, Error> {
+        self.connection.execute(sql, params)
+            .map_err(|err| Error::ExecuteError(Box::new(err)))
+    }
+
+    /// Runs a raw query, reading every result row into a `Row`.
+    pub fn query_raw<Row: FromRow>(&mut self, sql: &str, params: impl Params) -> Result<Vec<Row>, Error> {
+        let mut stmt = self.connection.prepare_cached(sql)
+            .map_err(|err| Error::StatementError(Box::new(err)))?;
+
+        stmt.query_map(params, Row::from_row)
+            .and_then(|rows| rows.collect())
+            .map_err(|err| Error::QueryError(Box::new(err)))
+    }
+
+    /// Runs `f` inside a transaction, committed when it returns `Ok` and rolled back otherwise.
+    ///
+    /// A failed rollback is ignored so the error returned by `f` is the one reported.
+    pub fn transaction<R>(&mut self, f: impl FnOnce(&mut Database<T>) -> Result<R, Error>) -> Result<R, Error> {
+        self.execute_raw("BEGIN", [])?;
+
+        let result = f(self).and_then(|value| self.execute_raw("COMMIT", []).map(|_| value));
+
+        if result.is_err() && !self.connection.is_autocommit() {
+            let _ = self.execute_raw("ROLLBACK", []);
+        }
+
+        result
+    }
+
+    /// Runs `f` with the underlying connection, for anything the query builders can't express.
+    ///
+    /// The connection is only borrowed for the duration of `f`, so it can't outlive the database.
+    pub fn with_connection<R>(&mut self, f: impl FnOnce(&mut Connection) -> R) -> R {
+        f(&mut self.connection)
+    }
+
+    /// Fetches the row that `child` refers to through its `#[belongs_to]` key.
+    pub fn parent<Child, Parent>(&mut self, child: &Child) -> Result<Option<Parent>, Error>
+    where
+        Child: BelongsTo<Parent>,
+        Parent: PrimaryKey,
+        T: SchemaHas<Parent>
+    {
+        let Some(key) = child.foreign_key() else {
+            return Ok(None);
+        };
+
+        QueryBuilder::<Parent, T>::new(&mut self.connection, Parent::NAME)
+            .filter(Column::<Parent, Parent::Key>::new(Parent::PRIMARY_KEY).eq(key.clone()))
+            .get()
+    }
+
+    /// Fetches every row that refers to `parent` through its `#[belongs_to]` key.
+    pub fn children<Parent, Child>(&mut self, parent: &Parent) -> Result<Vec<Child>, Error>
+    where
+        Child: BelongsTo<Parent>,
+        Parent: PrimaryKey,
+        T: SchemaHas<Child>
+    {
+        QueryBuilder::<Child, T>::new(&mut self.connection, Child::NAME)
+            .filter(Column::<Child, Parent::Key>::new(Child::FOREIGN_KEY).eq(parent.primary_key().clone()))
+            .all()
+    }
+
+    /// Fetches every `Other` linked to `row` through the `Through` join model.
+    pub fn related<Row, Through, Other>(&mut self, row: &Row) -> Result<Vec<Other>, Error>
+    where
+        Row: PrimaryKey,
+        Other: PrimaryKey,
+        Through: BelongsTo<Row> + BelongsTo<Other>,
+        T: SchemaHas<Row> + SchemaHas<Through> + SchemaHas<Other>
+    {
+        let mut sql = format!(
+            "SELECT {columns} FROM {other} INNER JOIN {through} ON {through}.{other_key} = {other}.{primary_key} WHERE {through}.{row_key} = ?1",
+            columns = select_list::<Other>(),
+            other = Other::NAME,
+            through = Through::NAME,
+            other_key = <Through as BelongsTo<Other>>::FOREIGN_KEY,
+            primary_key = Other::PRIMARY_KEY,
+            row_key = <Through as BelongsTo<Row>>::FOREIGN_KEY,
+        );
+
+        for (table, soft_delete) in [(Through::NAME, Through::SOFT_DELETE), (Other::NAME, Other::SOFT_DELETE)] {
+            if soft_delete {
+                sql.push_str(&format!(" AND {}.deleted_at IS NULL", table));
+            }
+        }
+
+        let mut stmt = self.connection.prepare_cached(sql.as_str())
+            .map_err(|err| Error::StatementError(Box::new(err)))?;
+
+        stmt.query_map([row.primary_key()], |row| Other::from_row_at(row, 0))
+            .and_then(|rows| rows.collect())
+            .map_err(|err| Error::QueryError(Box::new(err)))
+    }
+
+    /// Links `row` to `other` by inserting a `Through` join row.
+    pub fn link<Row, Through, Other>(&mut self, row: &Row, other: &Other) -> Result<(), Error>
+    where
+        Row: PrimaryKey,
+        Other: PrimaryKey,
+        Through: BelongsTo<Row> + BelongsTo<Other>,
+        T: SchemaHas<Row> + SchemaHas<Through> + SchemaHas<Other>
+    {
+        let columns = [<Through as BelongsTo<Row>>::FOREIGN_KEY, <Through as BelongsTo<Other>>::FOREIGN_KEY];
+        let params: [&dyn ToSql; 2] = [row.primary_key(), other.primary_key()];
+
+        InsertBuilder::new(&mut self.connection, Through::NAME, ())
+            .finish(&columns, params.as_slice())?;
+
+        Ok(())
+    }
+
+    /// Removes the `Through` join rows between `row` and `other`.
+    pub fn unlink<Row, Through, Other>(&mut self, row: &Row, other: &Other) -> Result<usize, Error>
+    where
+        Row: PrimaryKey,
+        Other: PrimaryKey,
+        Through: BelongsTo<Row> + BelongsTo<Other>,
+        T: SchemaHas<Row> + SchemaHas<Through> + SchemaHas<Other>
+    {
+        QueryBuilder::<Through, T>::new(&mut self.connection, Through::NAME)
+            .filter(Column::<Through, Row::Key>::new(<Through as BelongsTo<Row>>::FOREIGN_KEY).eq(row.primary_key().clone()))
+            .filter(Column::<Through, Other::Key>::new(<Through as BelongsTo<Other>>::FOREIGN_KEY).eq(other.primary_key().clone()))
+            .delete()
     }
-    */
 }
 
 /// Commonly used types for convenient importing.
 pub mod prelude {
     pub use crate::Database;
+    pub use crate::options::DatabaseConfig;
+    pub use crate::pool::Pool;
+    #[cfg(feature = "tokio")]
+    pub use crate::asynchronous::AsyncDatabase;
+    pub use crate::schema::query::Where;
+    pub use crate::schema::condition::{Condition, exists};
+    pub use crate::schema::aggregate::count;
 
-    pub use hell_orm_macro::{Schema, Model};
+    pub use hell_orm_macro::{Schema, Model, Projection, FromRow, on, query_as};
 }
 
 #[doc(hidden)]
@@ -0,0 +1,178 @@
+use crate::error::Error;
+
+use rusqlite::Connection;
+
+use std::time::Duration;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalMode {
+    Delete,
+    Truncate,
+    Persist,
+    Memory,
+    Wal,
+    Off,
+}
+
+impl JournalMode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            JournalMode::Delete => "DELETE",
+            JournalMode::Truncate => "TRUNCATE",
+            JournalMode::Persist => "PERSIST",
+            JournalMode::Memory => "MEMORY",
+            JournalMode::Wal => "WAL",
+            JournalMode::Off => "OFF",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Synchronous {
+    Off,
+    Normal,
+    Full,
+    Extra,
+}
+
+impl Synchronous {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Synchronous::Off => "OFF",
+            Synchronous::Normal => "NORMAL",
+            Synchronous::Full => "FULL",
+            Synchronous::Extra => "EXTRA",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TempStore {
+    Default,
+    File,
+    Memory,
+}
+
+impl TempStore {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TempStore::Default => "DEFAULT",
+            TempStore::File => "FILE",
+            TempStore::Memory => "MEMORY",
+        }
+    }
+}
+
+/// Connection settings applied when the database is opened, before the schema is created.
+///
+/// Settings that are never set keep SQLite's defaults.
+#[derive(Debug, Clone, Default)]
+pub struct DatabaseConfig {
+    journal_mode: Option<JournalMode>,
+    synchronous: Option<Synchronous>,
+    busy_timeout: Option<Duration>,
+    foreign_keys: Option<bool>,
+    cache_size: Option<i64>,
+    mmap_size: Option<u64>,
+    temp_store: Option<TempStore>,
+    statement_cache_capacity: Option<usize>,
+}
+
+impl DatabaseConfig {
+    pub fn new() -> DatabaseConfig {
+        DatabaseConfig::default()
+    }
+
+    pub fn journal_mode(mut self, mode: JournalMode) -> DatabaseConfig {
+        self.journal_mode = Some(mode);
+        self
+    }
+
+    /// Shorthand for `journal_mode(JournalMode::Wal)`.
+    pub fn wal(self) -> DatabaseConfig {
+        self.journal_mode(JournalMode::Wal)
+    }
+
+    pub fn synchronous(mut self, synchronous: Synchronous) -> DatabaseConfig {
+        self.synchronous = Some(synchronous);
+        self
+    }
+
+    /// How long a statement waits on a locked database before failing with `SQLITE_BUSY`.
+    pub fn busy_timeout(mut self, timeout: Duration) -> DatabaseConfig {
+        self.busy_timeout = Some(timeout);
+        self
+    }
+
+    pub fn foreign_keys(mut self, enabled: bool) -> DatabaseConfig {
+        self.foreign_keys = Some(enabled);
+        self
+    }
+
+    /// The page cache size, in pages when positive and in KiB when negative, as SQLite reads it.
+    pub fn cache_size(mut self, size: i64) -> DatabaseConfig {
+        self.cache_size = Some(size);
+        self
+    }
+
+    /// The number of bytes of the file to memory map, `0` disables memory mapping.
+    ///
+    /// SQLite reads the size as a signed 64 bit integer, so sizes above `i64::MAX` are clamped to it.
+    pub fn mmap_size(mut self, size: u64) -> DatabaseConfig {
+        self.mmap_size = Some(size);
+        self
+    }
+
+    pub fn temp_store(mut self, store: TempStore) -> DatabaseConfig {
+        self.temp_store = Some(store);
+        self
+    }
+
+    /// How many prepared statements the connection keeps for reuse, see `Database::set_statement_cache_capacity`.
+    pub fn statement_cache_capacity(mut self, capacity: usize) -> DatabaseConfig {
+        self.statement_cache_capacity = Some(capacity);
+        self
+    }
+
+    pub(crate) fn apply(&self, connection: &Connection) -> Result<(), Error> {
+        let pragma = |name: &str, value: &dyn rusqlite::ToSql| connection.pragma_update(None, name, value)
+            .map_err(|err| Error::OpenError(Box::new(err)));
+
+        if let Some(mode) = self.journal_mode {
+            pragma("journal_mode", &mode.as_str())?;
+        }
+
+        if let Some(synchronous) = self.synchronous {
+            pragma("synchronous", &synchronous.as_str())?;
+        }
+
+        if let Some(timeout) = self.busy_timeout {
+            connection.busy_timeout(timeout)
+                .map_err(|err| Error::OpenError(Box::new(err)))?;
+        }
+
+        if let Some(enabled) = self.foreign_keys {
+            pragma("foreign_keys", &enabled)?;
+        }
+
+        if let Some(size) = self.cache_size {
+            pragma("cache_size", &size)?;
+        }
+
+        if let Some(size) = self.mmap_size {
+            pragma("mmap_size", &(size.min(i64::MAX as u64) as i64))?;
+        }
+
+        if let Some(store) = self.temp_store {
+            pragma("temp_store", &store.as_str())?;
+        }
+
+        if let Some(capacity) = self.statement_cache_capacity {
+            connection.set_prepared_statement_cache_capacity(capacity);
+        }
+
+        Ok(())
+    }
+}
+
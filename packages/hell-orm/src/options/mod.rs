@@ -0,0 +1,50 @@
+//! Options for opening a database connection.
+
+pub mod config;
+
+use crate::error::Error;
+
+pub use config::{DatabaseConfig, JournalMode, Synchronous, TempStore};
+
+use rusqlite::Connection;
+
+pub use rusqlite::OpenFlags;
+
+use std::path::Path;
+
+
+/// How `Database::open_with` opens the connection.
+#[derive(Debug, Clone, Default)]
+pub struct OpenOptions {
+    /// The SQLite open flags, read-only connections verify the schema instead of creating it.
+    pub flags: OpenFlags,
+
+    /// The name of a registered VFS to open the file with.
+    pub vfs: Option<String>,
+
+    /// Pragmas applied as `PRAGMA name = value`, in order, before the schema is touched.
+    pub pragmas: Vec<(String, String)>,
+}
+
+impl OpenOptions {
+    pub fn is_read_only(&self) -> bool {
+        self.flags.contains(OpenFlags::SQLITE_OPEN_READ_ONLY)
+    }
+
+    pub(crate) fn open(&self, path: impl AsRef<Path>) -> Result<Connection, Error> {
+        let connection = match &self.vfs {
+            Some(vfs) => Connection::open_with_flags_and_vfs(path, self.flags, vfs.as_str()),
+            None => Connection::open_with_flags(path, self.flags),
+        };
+
+        let connection = connection.map_err(|err| Error::OpenError(Box::new(err)))?;
+
+        for (name, value) in &self.pragmas {
+            connection.pragma_update(None, name, value)
+                .map_err(|err| Error::OpenError(Box::new(err)))?;
+        }
+
+        Ok(connection)
+    }
+}
+
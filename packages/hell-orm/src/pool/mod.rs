@@ -0,0 +1,307 @@
+//! A pool of connections to one database file, shared between threads.
+
+use crate::options::{OpenOptions, OpenFlags, DatabaseConfig};
+use crate::schema::query::Query;
+use crate::schema::relation::BelongsTo;
+use crate::schema::row::FromRow;
+use crate::schema::{Model, PrimaryKey, Schema, SchemaHas};
+use crate::error::Error;
+use crate::Database;
+
+use rusqlite::Params;
+
+use std::cell::Cell;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
+use std::path::Path;
+
+
+/// A connection handed out by a `Slot`, and the thread that last used its handle.
+struct Holder {
+    handle: u64,
+    thread: ThreadId,
+}
+
+/// The connections of a `Slot`, split into idle ones and the holders of the others.
+struct Connections<T: Schema> {
+    idle: Vec<Database<T>>,
+    holders: Vec<Holder>,
+    next_handle: u64,
+}
+
+/// Idle connections of one kind, handed out one at a time.
+struct Slot<T: Schema> {
+    connections: Mutex<Connections<T>>,
+    available: Condvar,
+}
+
+impl<T: Schema> Slot<T> {
+    fn new(databases: Vec<Database<T>>) -> Slot<T> {
+        Slot {
+            connections: Mutex::new(Connections {
+                idle: databases,
+                holders: Vec::new(),
+                next_handle: 0,
+            }),
+            available: Condvar::new(),
+        }
+    }
+
+    /// Waits until a connection is idle, giving up after `timeout` if there is one, and returns it with its handle number.
+    ///
+    /// Fails with `Error::PoolReentered` instead of waiting when the calling thread holds every
+    /// connection itself, as nothing could ever hand one back.
+    ///
+    /// A panic while holding a handle cannot corrupt the list, so poisoning is ignored.
+    fn take(&self, timeout: Option<Duration>) -> Result<(Database<T>, u64), Error> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let current = thread::current().id();
+        let mut connections = self.connections.lock().unwrap_or_else(PoisonError::into_inner);
+
+        loop {
+            if let Some(database) = connections.idle.pop() {
+                let handle = connections.next_handle;
+
+                connections.next_handle += 1;
+                connections.holders.push(Holder {
+                    handle,
+                    thread: current,
+                });
+
+                return Ok((database, handle));
+            }
+
+            if connections.holders.iter().all(|holder| holder.thread == current) {
+                return Err(Error::PoolReentered);
+            }
+
+            connections = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.checked_duration_since(Instant::now())
+                        .filter(|remaining| !remaining.is_zero())
+                        .ok_or(Error::PoolTimeout)?;
+
+                    self.available.wait_timeout(connections, remaining).unwrap_or_else(PoisonError::into_inner).0
+                },
+                None => self.available.wait(connections).unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+
+    /// Records that `handle` is now used from `thread`, after it was moved there.
+    fn hold(&self, handle: u64, thread: ThreadId) {
+        let mut connections = self.connections.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(holder) = connections.holders.iter_mut().find(|holder| holder.handle == handle) {
+            holder.thread = thread;
+        }
+    }
+
+    /// Hands `database` back, taken out as `handle`.
+    fn put(&self, database: Database<T>, handle: u64) {
+        let mut connections = self.connections.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(index) = connections.holders.iter().position(|holder| holder.handle == handle) {
+            connections.holders.swap_remove(index);
+        }
+
+        connections.idle.push(database);
+        drop(connections);
+
+        self.available.notify_one();
+    }
+}
+
+/// A single writer connection plus a fixed number of read-only connections to the same file.
+///
+/// Writes are serialized through the one writer, while readers run alongside it when the
+/// database uses `JournalMode::Wal`. Handles go back to the pool when dropped.
+///
+/// `writer` and `reader` wait as long as it takes for an idle connection, unless the calling thread
+/// already holds every connection of that kind, such as asking for the writer twice, which fails
+/// with `Error::PoolReentered`. The `try_` and `_timeout` variants give up instead of waiting.
+///
+/// Handles can be moved to other threads, a handle counts as held by the thread that last used it.
+/// A handle sent away but not used yet still counts for the sender.
+pub struct Pool<T: Schema> {
+    writer: Slot<T>,
+    readers: Slot<T>,
+}
+
+impl<T: Schema> Pool<T> {
+    /// Opens the writer first, which creates the schema, then `readers` read-only connections applying the same `config`.
+    ///
+    /// Readers never fall back to the writer, so a pool needs at least one of them.
+    pub fn open(path: impl AsRef<Path>, readers: usize, config: DatabaseConfig) -> Result<Pool<T>, Error> {
+        if readers == 0 {
+            return Err(Error::OpenError("a pool needs at least one reader".into()));
+        }
+
+        let path = path.as_ref();
+        let writer = Database::open(path, config.clone())?;
+
+        let readers = (0..readers)
+            .map(|_| {
+                let options = OpenOptions {
+                    flags: OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+                    ..OpenOptions::default()
+                };
+
+                Database::open_configured(path, &options, &config)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Pool {
+            writer: Slot::new(vec![writer]),
+            readers: Slot::new(readers),
+        })
+    }
+
+    /// Waits for the writer, only one writer handle exists at a time.
+    ///
+    /// Fails with `Error::PoolReentered` when the calling thread already holds the writer.
+    pub fn writer(&self) -> Result<PooledDatabase<'_, T>, Error> {
+        PooledDatabase::take(&self.writer, None)
+    }
+
+    /// The writer if it is idle right now.
+    pub fn try_writer(&self) -> Option<PooledDatabase<'_, T>> {
+        PooledDatabase::take(&self.writer, Some(Duration::ZERO)).ok()
+    }
+
+    /// Waits up to `timeout` for the writer, failing with `Error::PoolTimeout` after that.
+    pub fn writer_timeout(&self, timeout: Duration) -> Result<PooledDatabase<'_, T>, Error> {
+        PooledDatabase::take(&self.writer, Some(timeout))
+    }
+
+    /// Waits for an idle read-only connection.
+    ///
+    /// Fails with `Error::PoolReentered` when the calling thread already holds every reader.
+    pub fn reader(&self) -> Result<PooledReader<'_, T>, Error> {
+        PooledReader::take(&self.readers, None)
+    }
+
+    /// A read-only connection if one is idle right now.
+    pub fn try_reader(&self) -> Option<PooledReader<'_, T>> {
+        PooledReader::take(&self.readers, Some(Duration::ZERO)).ok()
+    }
+
+    /// Waits up to `timeout` for a read-only connection, failing with `Error::PoolTimeout` after that.
+    pub fn reader_timeout(&self, timeout: Duration) -> Result<PooledReader<'_, T>, Error> {
+        PooledReader::take(&self.readers, Some(timeout))
+    }
+}
+
+/// A connection borrowed from a `Pool`, with the whole `Database` API through `Deref`.
+pub struct PooledDatabase<'a, T: Schema> {
+    database: Option<Database<T>>,
+    handle: u64,
+    holder: Cell<ThreadId>,
+    slot: &'a Slot<T>,
+}
+
+impl<'a, T: Schema> PooledDatabase<'a, T> {
+    fn take(slot: &'a Slot<T>, timeout: Option<Duration>) -> Result<PooledDatabase<'a, T>, Error> {
+        slot.take(timeout).map(|(database, handle)| PooledDatabase {
+            database: Some(database),
+            handle,
+            holder: Cell::new(thread::current().id()),
+            slot,
+        })
+    }
+
+    /// Moves the handle over to the calling thread in the pool, if it was last used from another one.
+    fn claim(&self) {
+        let current = thread::current().id();
+
+        if self.holder.get() != current {
+            self.holder.set(current);
+            self.slot.hold(self.handle, current);
+        }
+    }
+}
+
+impl<'a, T: Schema> Deref for PooledDatabase<'a, T> {
+    type Target = Database<T>;
+
+    fn deref(&self) -> &Database<T> {
+        self.claim();
+        self.database.as_ref().expect("pooled connection is only taken on drop")
+    }
+}
+
+impl<'a, T: Schema> DerefMut for PooledDatabase<'a, T> {
+    fn deref_mut(&mut self) -> &mut Database<T> {
+        self.claim();
+        self.database.as_mut().expect("pooled connection is only taken on drop")
+    }
+}
+
+impl<'a, T: Schema> Drop for PooledDatabase<'a, T> {
+    fn drop(&mut self) {
+        if let Some(database) = self.database.take() {
+            self.slot.put(database, self.handle);
+        }
+    }
+}
+
+/// A read-only connection borrowed from a `Pool`, exposing only the reading part of the `Database` API.
+///
+/// The connection is opened read-only, so an update or delete started from `query` fails when it runs.
+pub struct PooledReader<'a, T: Schema> {
+    database: PooledDatabase<'a, T>,
+}
+
+impl<'a, T: Schema> PooledReader<'a, T> {
+    fn take(slot: &'a Slot<T>, timeout: Option<Duration>) -> Result<PooledReader<'a, T>, Error> {
+        PooledDatabase::take(slot, timeout).map(|database| PooledReader {
+            database,
+        })
+    }
+
+    /// See `Database::query`.
+    pub fn query<'b, Row: Model>(&'b mut self) -> <Row as Query>::Builder<'b, T>
+    where
+        T: SchemaHas<Row>
+    {
+        self.database.query::<Row>()
+    }
+
+    /// See `Database::query_raw`.
+    pub fn query_raw<Row: FromRow>(&mut self, sql: &str, params: impl Params) -> Result<Vec<Row>, Error> {
+        self.database.query_raw(sql, params)
+    }
+
+    /// See `Database::parent`.
+    pub fn parent<Child, Parent>(&mut self, child: &Child) -> Result<Option<Parent>, Error>
+    where
+        Child: BelongsTo<Parent>,
+        Parent: PrimaryKey,
+        T: SchemaHas<Parent>
+    {
+        self.database.parent(child)
+    }
+
+    /// See `Database::children`.
+    pub fn children<Parent, Child>(&mut self, parent: &Parent) -> Result<Vec<Child>, Error>
+    where
+        Child: BelongsTo<Parent>,
+        Parent: PrimaryKey,
+        T: SchemaHas<Child>
+    {
+        self.database.children(parent)
+    }
+
+    /// See `Database::related`.
+    pub fn related<Row, Through, Other>(&mut self, row: &Row) -> Result<Vec<Other>, Error>
+    where
+        Row: PrimaryKey,
+        Other: PrimaryKey,
+        Through: BelongsTo<Row> + BelongsTo<Other>,
+        T: SchemaHas<Row> + SchemaHas<Through> + SchemaHas<Other>
+    {
+        self.database.related::<Row, Through, Other>(row)
+    }
+}
@@ -0,0 +1,167 @@
+use crate::schema::column::{Column, IntoValue};
+use crate::schema::condition::{Condition, Expr};
+use crate::schema::query::{QueryBuilder, Where, prepare};
+use crate::schema::select::Selection;
+use crate::schema::Model;
+use crate::error::Error;
+
+use rusqlite::ToSql;
+
+use std::marker::PhantomData;
+
+
+/// An aggregate over the rows of `M` evaluating to a `T`, usable in `select` and `having`.
+pub struct Aggregate<M, T> {
+    pub(crate) sql: String,
+    _marker: PhantomData<fn() -> (M, T)>,
+}
+
+impl<M, T> Aggregate<M, T> {
+    pub(crate) fn new(sql: String) -> Aggregate<M, T> {
+        Aggregate {
+            sql,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<M, T: ToSql + 'static> Aggregate<M, T> {
+    pub fn compare(self, condition: Where<T>) -> Condition<M> {
+        Condition::new(Expr::Compare(self.sql, condition.operator(), Box::new(condition.into_value())))
+    }
+
+    pub fn eq(self, value: impl IntoValue<T>) -> Condition<M> {
+        self.compare(Where::Equal(value.into_value()))
+    }
+
+    pub fn ne(self, value: impl IntoValue<T>) -> Condition<M> {
+        self.compare(Where::NotEqual(value.into_value()))
+    }
+
+    pub fn lt(self, value: impl IntoValue<T>) -> Condition<M> {
+        self.compare(Where::LessThan(value.into_value()))
+    }
+
+    pub fn gt(self, value: impl IntoValue<T>) -> Condition<M> {
+        self.compare(Where::GreaterThan(value.into_value()))
+    }
+
+    pub fn le(self, value: impl IntoValue<T>) -> Condition<M> {
+        self.compare(Where::LessOrEqual(value.into_value()))
+    }
+
+    pub fn ge(self, value: impl IntoValue<T>) -> Condition<M> {
+        self.compare(Where::GreaterOrEqual(value.into_value()))
+    }
+}
+
+/// `COUNT(*)`, the number of rows in the query or group.
+pub fn count<M>() -> Aggregate<M, i64> {
+    Aggregate::new(String::from("COUNT(*)"))
+}
+
+/// Column types that `SUM` and `AVG` can add up.
+pub trait Numeric {}
+
+macro_rules! impl_numeric {
+    ($($ty:ty),*) => {
+        $(impl Numeric for $ty {})*
+    };
+}
+
+impl_numeric!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl<T: Numeric> Numeric for Option<T> {}
+
+impl<M: Model, T: Numeric> Column<M, T> {
+    /// `SUM(column)`, `None` when there are no values to add up.
+    pub fn sum(self) -> Aggregate<M, Option<T>> {
+        Aggregate::new(format!("SUM({})", self.qualified()))
+    }
+
+    /// `AVG(column)`, `None` when there are no values to average.
+    pub fn avg(self) -> Aggregate<M, Option<f64>> {
+        Aggregate::new(format!("AVG({})", self.qualified()))
+    }
+}
+
+impl<M: Model, T> Column<M, T> {
+    /// `COUNT(column)`, the number of rows where the column is not null.
+    pub fn count(self) -> Aggregate<M, i64> {
+        Aggregate::new(format!("COUNT({})", self.qualified()))
+    }
+
+    pub fn min(self) -> Aggregate<M, Option<T>> {
+        Aggregate::new(format!("MIN({})", self.qualified()))
+    }
+
+    pub fn max(self) -> Aggregate<M, Option<T>> {
+        Aggregate::new(format!("MAX({})", self.qualified()))
+    }
+}
+
+pub struct GroupBuilder<'a, Row, S> {
+    query: QueryBuilder<'a, Row, S>,
+    columns: Vec<String>,
+    having: Vec<Condition<Row>>,
+}
+
+impl<'a, Row: Model, S> GroupBuilder<'a, Row, S> {
+    pub fn new(query: QueryBuilder<'a, Row, S>, column: String) -> GroupBuilder<'a, Row, S> {
+        GroupBuilder {
+            query,
+            columns: vec![column],
+            having: Vec::new(),
+        }
+    }
+
+    /// Groups by another column, after the earlier ones.
+    pub fn group_by<T>(mut self, column: Column<Row, T>) -> GroupBuilder<'a, Row, S> {
+        self.columns.push(column.qualified());
+        self
+    }
+
+    /// Keeps the groups matching `condition`, usually built from an aggregate such as `count().gt(1)`.
+    pub fn having(mut self, condition: Condition<Row>) -> GroupBuilder<'a, Row, S> {
+        self.having.push(condition);
+        self
+    }
+
+    /// Runs the query, returning one `selection` per group.
+    pub fn select<Sel: Selection<Row>>(self, selection: Sel) -> Result<Vec<Sel::Output>, Error> {
+        let mut index = self.query.params().count();
+
+        let mut sql = format!(
+            "{}{} {} FROM {}{} GROUP BY {}",
+            self.query.with_clause(0),
+            self.query.select_keyword(),
+            selection.columns().join(", "),
+            Row::NAME,
+            self.query.where_clause(0),
+            self.columns.join(", "),
+        );
+
+        if !self.having.is_empty() {
+            let having = self.having.iter()
+                .map(|condition| condition.render(&mut index))
+                .collect::<Vec<String>>()
+                .join(" AND ");
+
+            sql.push_str(&format!(" HAVING {}", having));
+        }
+
+        sql.push_str(&self.query.order_clause());
+
+        let params = self.query.params()
+            .chain(self.having.iter().flat_map(|condition| condition.params()));
+
+        let variable = self.query.is_variable() || self.having.iter().any(Condition::is_variable);
+
+        let mut stmt = prepare(self.query.connection, sql.as_str(), variable)?;
+
+        stmt.query_map(rusqlite::params_from_iter(params), |row| Sel::decode(row, 0))
+            .and_then(|rows| rows.collect())
+            .map_err(|err| Error::QueryError(Box::new(err)))
+    }
+}
+
@@ -0,0 +1,76 @@
+//! The runtime side of `query_as!`, which expands into a `CheckedQuery` built from these helpers.
+
+use crate::schema::column::{Column, IntoValue};
+use crate::schema::{Model, Schema};
+use crate::error::Error;
+use crate::Database;
+
+use rusqlite::types::FromSql;
+use rusqlite::{Row, ToSql};
+
+use std::marker::PhantomData;
+
+
+/// A raw SQL query checked against the schema `S` at compile time, returning rows of type `T`.
+pub struct CheckedQuery<S, T> {
+    sql: &'static str,
+    params: Vec<Box<dyn ToSql>>,
+    decode: fn(&Row) -> Result<T, rusqlite::Error>,
+    _marker: PhantomData<S>,
+}
+
+impl<S: Schema, T> CheckedQuery<S, T> {
+    pub fn new(sql: &'static str, params: Vec<Box<dyn ToSql>>, decode: fn(&Row) -> Result<T, rusqlite::Error>) -> CheckedQuery<S, T> {
+        CheckedQuery {
+            sql,
+            params,
+            decode,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn all(self, db: &mut Database<S>) -> Result<Vec<T>, Error> {
+        let mut stmt = db.connection.prepare_cached(self.sql)
+            .map_err(|err| Error::StatementError(Box::new(err)))?;
+
+        stmt.query_map(rusqlite::params_from_iter(self.params.iter()), self.decode)
+            .and_then(|rows| rows.collect())
+            .map_err(|err| Error::QueryError(Box::new(err)))
+    }
+
+    pub fn get(self, db: &mut Database<S>) -> Result<Option<T>, Error> {
+        self.all(db).map(|rows| rows.into_iter().next())
+    }
+}
+
+/// Binds `value` to a placeholder compared with `column`, so it must be a value of the column type.
+pub fn bind<M, T: ToSql + 'static>(_column: Column<M, T>, value: impl IntoValue<T>) -> Box<dyn ToSql> {
+    Box::new(value.into_value())
+}
+
+/// Binds `value` to a placeholder whose type could not be inferred.
+pub fn bind_any(value: impl ToSql + 'static) -> Box<dyn ToSql> {
+    Box::new(value)
+}
+
+/// Decodes the value of `column` at `offset`, moving `offset` past it.
+pub fn decode<M, T: FromSql>(_column: Column<M, T>, row: &Row, offset: &mut usize) -> Result<T, rusqlite::Error> {
+    *offset += 1;
+
+    row.get(*offset - 1)
+}
+
+/// Decodes a `COUNT(...)` at `offset`, moving `offset` past it.
+pub fn decode_count(row: &Row, offset: &mut usize) -> Result<i64, rusqlite::Error> {
+    *offset += 1;
+
+    row.get(*offset - 1)
+}
+
+/// Decodes every column of `M` selected with `table.*`, moving `offset` past them.
+pub fn decode_model<M: Model>(row: &Row, offset: &mut usize) -> Result<M, rusqlite::Error> {
+    *offset += M::COLUMNS.len();
+
+    M::from_row_at(row, *offset - M::COLUMNS.len())
+}
+
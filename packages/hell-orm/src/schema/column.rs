@@ -0,0 +1,192 @@
+use crate::schema::condition::{Condition, Expr};
+use crate::schema::query::Where;
+use crate::schema::subquery::{Subquery, WithTable};
+use crate::schema::Model;
+
+use rusqlite::ToSql;
+
+use std::marker::PhantomData;
+
+
+/// Values accepted when comparing against a column of type `T`.
+pub trait IntoValue<T> {
+    fn into_value(self) -> T;
+}
+
+impl<T> IntoValue<T> for T {
+    fn into_value(self) -> T {
+        self
+    }
+}
+
+impl<T> IntoValue<Option<T>> for T {
+    fn into_value(self) -> Option<T> {
+        Some(self)
+    }
+}
+
+impl IntoValue<String> for &str {
+    fn into_value(self) -> String {
+        self.to_string()
+    }
+}
+
+/// The right hand side of `Column::in_`, a list of values, a `Subquery` or a `WithTable` selecting values of type `T`.
+pub trait InValues<M, T> {
+    fn in_values(self, column: String) -> Condition<M>;
+}
+
+impl<M, T: ToSql + 'static, I: IntoIterator> InValues<M, T> for I
+where
+    I::Item: IntoValue<T>
+{
+    fn in_values(self, column: String) -> Condition<M> {
+        let values = self.into_iter()
+            .map(|value| Box::new(value.into_value()) as Box<dyn ToSql>)
+            .collect();
+
+        Condition::new(Expr::In(column, values))
+    }
+}
+
+impl<M, T> InValues<M, T> for Subquery<T> {
+    fn in_values(self, column: String) -> Condition<M> {
+        Condition::new(Expr::InSelect(column, self.select))
+    }
+}
+
+impl<M, T> InValues<M, T> for WithTable<T> {
+    fn in_values(self, column: String) -> Condition<M> {
+        Condition::new(Expr::InTable(column, self.name))
+    }
+}
+
+/// A column of the model `M` holding values of type `T`, generated as `M::{FIELD}_COL` for every field.
+pub struct Column<M, T> {
+    pub name: &'static str,
+    _marker: PhantomData<fn() -> (M, T)>,
+}
+
+impl<M, T> Column<M, T> {
+    pub const fn new(name: &'static str) -> Column<M, T> {
+        Column {
+            name,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A sort key on the rows of the model `M`, built with `Column::asc` and `Column::desc`.
+pub struct Order<M> {
+    pub(crate) name: &'static str,
+    pub(crate) direction: &'static str,
+    _marker: PhantomData<fn() -> M>,
+}
+
+impl<M: Model> Order<M> {
+    pub(crate) fn new(name: &'static str, direction: &'static str) -> Order<M> {
+        Order {
+            name,
+            direction,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The same sort key in the opposite direction.
+    pub(crate) fn reversed(&self) -> Order<M> {
+        Order::new(self.name, if self.direction == "ASC" { "DESC" } else { "ASC" })
+    }
+
+    pub fn render(&self) -> String {
+        format!("{}.{} {}", M::NAME, self.name, self.direction)
+    }
+}
+
+impl<M: Model, T> Column<M, T> {
+    /// The table name of `M`.
+    pub fn table(&self) -> &'static str {
+        M::NAME
+    }
+
+    /// The column name qualified with the table name of `M`.
+    pub fn qualified(&self) -> String {
+        format!("{}.{}", M::NAME, self.name)
+    }
+
+    /// Sorts by the column in ascending order.
+    pub fn asc(self) -> Order<M> {
+        Order::new(self.name, "ASC")
+    }
+
+    /// Sorts by the column in descending order.
+    pub fn desc(self) -> Order<M> {
+        Order::new(self.name, "DESC")
+    }
+}
+
+impl<M: Model, T: ToSql + 'static> Column<M, T> {
+    pub fn compare(self, condition: Where<T>) -> Condition<M> {
+        Condition::new(Expr::Compare(self.qualified(), condition.operator(), Box::new(condition.into_value())))
+    }
+
+    pub fn eq(self, value: impl IntoValue<T>) -> Condition<M> {
+        self.compare(Where::Equal(value.into_value()))
+    }
+
+    pub fn ne(self, value: impl IntoValue<T>) -> Condition<M> {
+        self.compare(Where::NotEqual(value.into_value()))
+    }
+
+    pub fn lt(self, value: impl IntoValue<T>) -> Condition<M> {
+        self.compare(Where::LessThan(value.into_value()))
+    }
+
+    pub fn gt(self, value: impl IntoValue<T>) -> Condition<M> {
+        self.compare(Where::GreaterThan(value.into_value()))
+    }
+
+    pub fn le(self, value: impl IntoValue<T>) -> Condition<M> {
+        self.compare(Where::LessOrEqual(value.into_value()))
+    }
+
+    pub fn ge(self, value: impl IntoValue<T>) -> Condition<M> {
+        self.compare(Where::GreaterOrEqual(value.into_value()))
+    }
+
+    /// Matches rows where the column equals any of `values`, a list of values or a subquery.
+    pub fn in_(self, values: impl InValues<M, T>) -> Condition<M> {
+        values.in_values(self.qualified())
+    }
+
+    /// Matches rows where the column lies between `low` and `high`, inclusive.
+    pub fn between(self, low: impl IntoValue<T>, high: impl IntoValue<T>) -> Condition<M> {
+        Condition::new(Expr::Between(self.qualified(), Box::new(low.into_value()), Box::new(high.into_value())))
+    }
+
+    /// Matches rows against a case insensitive `LIKE` pattern, using `%` and `_` as wildcards.
+    pub fn like(self, pattern: impl Into<String>) -> Condition<M> {
+        Condition::new(Expr::Compare(self.qualified(), "LIKE", Box::new(pattern.into())))
+    }
+
+    /// Matches rows against a case sensitive `GLOB` pattern, using `*` and `?` as wildcards.
+    pub fn glob(self, pattern: impl Into<String>) -> Condition<M> {
+        Condition::new(Expr::Compare(self.qualified(), "GLOB", Box::new(pattern.into())))
+    }
+
+    pub fn is_null(self) -> Condition<M> {
+        Condition::new(Expr::Null(self.qualified(), true))
+    }
+
+    pub fn is_not_null(self) -> Condition<M> {
+        Condition::new(Expr::Null(self.qualified(), false))
+    }
+}
+
+impl<M, T> Clone for Column<M, T> {
+    fn clone(&self) -> Column<M, T> {
+        *self
+    }
+}
+
+impl<M, T> Copy for Column<M, T> {}
+
@@ -0,0 +1,142 @@
+use crate::schema::subquery::{Fragment, Subquery};
+
+use rusqlite::ToSql;
+
+use std::marker::PhantomData;
+
+
+/// The next `?N` placeholder after the `index` already bound parameters.
+pub(crate) fn placeholder(index: &mut usize) -> String {
+    *index += 1;
+
+    format!("?{}", index)
+}
+
+/// `count` comma separated placeholders following the `index` already bound parameters.
+pub(crate) fn placeholders(index: &mut usize, count: usize) -> String {
+    (0..count)
+        .map(|_| placeholder(index))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+
+pub(crate) enum Expr {
+    Compare(String, &'static str, Box<dyn ToSql>),
+    In(String, Vec<Box<dyn ToSql>>),
+    Tuple(Vec<String>, &'static str, Vec<Box<dyn ToSql>>),
+    Between(String, Box<dyn ToSql>, Box<dyn ToSql>),
+    Null(String, bool),
+    InSelect(String, Box<dyn Fragment>),
+    InTable(String, &'static str),
+    Exists(Box<dyn Fragment>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+}
+
+impl Expr {
+    /// Whether the rendered SQL depends on how many values are bound, as with `IN` lists.
+    fn is_variable(&self) -> bool {
+        match self {
+            Expr::In(_, _) => true,
+            Expr::InSelect(_, select) | Expr::Exists(select) => select.is_variable(),
+            Expr::And(left, right) | Expr::Or(left, right) => left.is_variable() || right.is_variable(),
+            Expr::Not(expr) => expr.is_variable(),
+            Expr::Compare(_, _, _) | Expr::Tuple(_, _, _) | Expr::Between(_, _, _) | Expr::Null(_, _) | Expr::InTable(_, _) => false,
+        }
+    }
+
+    fn render(&self, index: &mut usize) -> String {
+        match self {
+            Expr::Compare(column, operator, _) => format!("{} {} {}", column, operator, placeholder(index)),
+            Expr::In(column, values) => format!("{} IN ({})", column, placeholders(index, values.len())),
+            Expr::Tuple(columns, operator, values) => format!("({}) {} ({})", columns.join(", "), operator, placeholders(index, values.len())),
+            Expr::Between(column, _, _) => {
+                let low = placeholder(index);
+                let high = placeholder(index);
+
+                format!("{} BETWEEN {} AND {}", column, low, high)
+            },
+            Expr::InSelect(column, select) => format!("{} IN ({})", column, select.render(index)),
+            Expr::InTable(column, table) => format!("{} IN {}", column, table),
+            Expr::Exists(select) => format!("EXISTS ({})", select.render(index)),
+            Expr::Null(column, true) => format!("{} IS NULL", column),
+            Expr::Null(column, false) => format!("{} IS NOT NULL", column),
+            Expr::And(left, right) => format!("({} AND {})", left.render(index), right.render(index)),
+            Expr::Or(left, right) => format!("({} OR {})", left.render(index), right.render(index)),
+            Expr::Not(expr) => format!("NOT ({})", expr.render(index)),
+        }
+    }
+
+    fn params<'a>(&'a self, params: &mut Vec<&'a dyn ToSql>) {
+        match self {
+            Expr::Compare(_, _, value) => params.push(value.as_ref()),
+            Expr::In(_, values) | Expr::Tuple(_, _, values) => params.extend(values.iter().map(|value| value.as_ref())),
+            Expr::Between(_, low, high) => params.extend([low.as_ref(), high.as_ref()]),
+            Expr::Null(_, _) | Expr::InTable(_, _) => {},
+            Expr::InSelect(_, select) | Expr::Exists(select) => params.extend(select.params()),
+            Expr::And(left, right) | Expr::Or(left, right) => {
+                left.params(params);
+                right.params(params);
+            },
+            Expr::Not(expr) => expr.params(params),
+        }
+    }
+}
+
+/// `EXISTS (subquery)`, matching when the subquery returns any row.
+pub fn exists<M, T>(subquery: Subquery<T>) -> Condition<M> {
+    Condition::new(Expr::Exists(subquery.select))
+}
+
+/// A filter on the rows of the model `M`, built from its typed columns such as `User::NAME_COL.eq("admin")`.
+pub struct Condition<M> {
+    expr: Expr,
+    _marker: PhantomData<fn() -> M>,
+}
+
+impl<M> Condition<M> {
+    pub(crate) fn new(expr: Expr) -> Condition<M> {
+        Condition {
+            expr,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Matches rows matching both `self` and `other`.
+    pub fn and(self, other: Condition<M>) -> Condition<M> {
+        Condition::new(Expr::And(Box::new(self.expr), Box::new(other.expr)))
+    }
+
+    /// Matches rows matching either `self` or `other`.
+    pub fn or(self, other: Condition<M>) -> Condition<M> {
+        Condition::new(Expr::Or(Box::new(self.expr), Box::new(other.expr)))
+    }
+
+    /// Matches rows not matching `self`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Condition<M> {
+        Condition::new(Expr::Not(Box::new(self.expr)))
+    }
+
+    /// Whether the rendered SQL changes with the number of bound values, which keeps it out of the statement cache.
+    pub(crate) fn is_variable(&self) -> bool {
+        self.expr.is_variable()
+    }
+
+    /// Renders the condition, numbering its placeholders after the `index` already bound parameters.
+    pub fn render(&self, index: &mut usize) -> String {
+        self.expr.render(index)
+    }
+
+    /// The bound parameters in placeholder order.
+    pub fn params(&self) -> Vec<&dyn ToSql> {
+        let mut params = Vec::new();
+
+        self.expr.params(&mut params);
+
+        params
+    }
+}
+
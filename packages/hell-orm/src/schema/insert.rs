@@ -1,3 +1,4 @@
+use crate::schema::condition::placeholders;
 use crate::error::Error;
 
 use rusqlite::{Connection, Params};
@@ -25,14 +26,9 @@ impl<'a, T> InsertBuilder<'a, T> {
     }
 
     pub fn finish(self, columns: &[&str], params: impl Params) -> Result<usize, Error> {
-        let placeholders = (1..=columns.len())
-            .map(|index| format!("?{}", index))
-            .collect::<Vec<String>>()
-            .join(", ");
+        let sql = format!("INSERT INTO {} ({}) VALUES ({})", self.table_name, columns.join(","), placeholders(&mut 0, columns.len()));
 
-        let sql = format!("INSERT INTO {} ({}) VALUES ({})", self.table_name, columns.join(","), placeholders);
-
-        let mut stmt = self.connection.prepare(sql.as_str())
+        let mut stmt = self.connection.prepare_cached(sql.as_str())
             .map_err(|err| Error::StatementError(Box::new(err)))?;
 
         stmt.execute(params)
@@ -0,0 +1,140 @@
+use crate::schema::query::{QueryBuilder, prepare, select_list, select_list_as};
+use crate::schema::column::Column;
+use crate::schema::Model;
+use crate::error::Error;
+
+use rusqlite::types::Type;
+use rusqlite::Row;
+
+use std::marker::PhantomData;
+
+
+/// The join condition between two models, usually built with `on!(Left::field == Right::field)`.
+pub struct On<L, R> {
+    pub(crate) left: &'static str,
+    pub(crate) right: &'static str,
+    _marker: PhantomData<fn() -> (L, R)>,
+}
+
+/// Column types that can be compared in a join, the same type or a nullable version of it.
+pub trait Joinable<T> {}
+
+impl<T> Joinable<T> for T {}
+
+impl<T> Joinable<T> for Option<T> {}
+
+impl<T> Joinable<Option<T>> for T {}
+
+impl<L: Model, R: Model> On<L, R> {
+    /// Both columns must hold the same type, or one of them a nullable version of it.
+    pub fn new<A: Joinable<B>, B>(left: Column<L, A>, right: Column<R, B>) -> On<L, R> {
+        On {
+            left: left.name,
+            right: right.name,
+            _marker: PhantomData,
+        }
+    }
+}
+
+pub trait JoinKind {
+    const SQL: &'static str;
+
+    type Output<M>;
+
+    fn decode<M: Model>(row: &Row, offset: usize) -> Result<Self::Output<M>, rusqlite::Error>;
+}
+
+/// `INNER JOIN`, every result has a joined row.
+pub struct Inner;
+
+impl JoinKind for Inner {
+    const SQL: &'static str = "INNER JOIN";
+
+    type Output<M> = M;
+
+    fn decode<M: Model>(row: &Row, offset: usize) -> Result<M, rusqlite::Error> {
+        M::from_row_at(row, offset)
+    }
+}
+
+/// `LEFT JOIN`, the joined row is `None` when nothing matched.
+pub struct Left;
+
+impl JoinKind for Left {
+    const SQL: &'static str = "LEFT JOIN";
+
+    type Output<M> = Option<M>;
+
+    fn decode<M: Model>(row: &Row, offset: usize) -> Result<Option<M>, rusqlite::Error> {
+        for index in offset..offset + M::COLUMNS.len() {
+            if row.get_ref(index)?.data_type() != Type::Null {
+                return M::from_row_at(row, offset).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// The alias of the joined table, so a model joined with itself still names both sides apart.
+const JOINED: &str = "__joined";
+
+/// A result row of a join, `(Row, Other)` or `(Row, Option<Other>)` for a left join.
+pub type Joined<Row, Other, Kind> = (Row, <Kind as JoinKind>::Output<Other>);
+
+pub struct JoinBuilder<'a, Row, Other, S, Kind> {
+    query: QueryBuilder<'a, Row, S>,
+    on: On<Row, Other>,
+    _marker: PhantomData<Kind>,
+}
+
+impl<'a, Row: Model, Other: Model, S, Kind: JoinKind> JoinBuilder<'a, Row, Other, S, Kind> {
+    pub fn new(query: QueryBuilder<'a, Row, S>, on: On<Row, Other>) -> JoinBuilder<'a, Row, Other, S, Kind> {
+        JoinBuilder {
+            query,
+            on,
+            _marker: PhantomData,
+        }
+    }
+
+    fn select(&self) -> Result<Vec<Joined<Row, Other, Kind>>, Error> {
+        let mut sql = format!(
+            "{}{} {}, {} FROM {} {} {} AS {} ON {}.{} = {}.{}",
+            self.query.with_clause(0),
+            self.query.select_keyword(),
+            select_list::<Row>(),
+            select_list_as::<Other>(JOINED),
+            Row::NAME,
+            Kind::SQL,
+            Other::NAME,
+            JOINED,
+            Row::NAME,
+            self.on.left,
+            JOINED,
+            self.on.right,
+        );
+
+        if Other::SOFT_DELETE {
+            sql.push_str(&format!(" AND {}.deleted_at IS NULL", JOINED));
+        }
+
+        sql.push_str(&self.query.where_clause(0));
+        sql.push_str(&self.query.order_clause());
+
+        let mut stmt = prepare(self.query.connection, sql.as_str(), self.query.is_variable())?;
+
+        stmt.query_map(rusqlite::params_from_iter(self.query.params()), |row| Ok((Row::from_row_at(row, 0)?, Kind::decode::<Other>(row, Row::COLUMNS.len())?)))
+            .and_then(|rows| rows.collect())
+            .map_err(|err| Error::QueryError(Box::new(err)))
+    }
+
+    pub fn get(mut self) -> Result<Option<Joined<Row, Other, Kind>>, Error> {
+        self.query = self.query.single();
+        self.select().map(|rows| rows.into_iter().next())
+    }
+
+    pub fn all(self) -> Result<Vec<Joined<Row, Other, Kind>>, Error> {
+        self.select()
+    }
+}
+
@@ -2,36 +2,86 @@
 
 pub mod insert;
 pub mod query;
+pub mod update;
+pub mod relation;
+pub mod column;
+pub mod condition;
+pub mod join;
+pub mod recursive;
+pub mod page;
+pub mod aggregate;
+pub mod select;
+pub mod subquery;
+pub mod table;
+pub mod checked;
+pub mod row;
+pub mod timestamp;
 
 use crate::error::Error;
 
 use insert::Insert;
+use query::Query;
+use row::FromRow;
 
-use rusqlite::Connection;
+use rusqlite::{Connection, ToSql};
 
 
-pub trait Model: Insert {
+/// Models decode through their `FromRow` impl, which reads `COLUMNS` in order from the given offset.
+pub trait Model: Insert + Query + FromRow + Sized {
     const NAME: &'static str;
 
     const COLUMNS: &'static [(&'static str, &'static str)];
+
+    /// Whether deletes only set the `deleted_at` column, see `#[soft_delete]`.
+    const SOFT_DELETE: bool = false;
+
+    /// The column incremented on every update, see `#[version]`.
+    const VERSION: Option<&'static str> = None;
+
+    /// Foreign keys as `(column, parent table, parent column)`, see `#[belongs_to]`.
+    const FOREIGN_KEYS: &'static [(&'static str, &'static str, &'static str)] = &[];
+}
+
+/// Implemented for models with a `#[primary_key]` field.
+pub trait PrimaryKey: Model {
+    type Key: ToSql + Clone + 'static;
+
+    const PRIMARY_KEY: &'static str;
+
+    fn primary_key(&self) -> &Self::Key;
+}
+
+/// Implemented for models with a `#[version]` field.
+pub trait Versioned: Model {
+    type Version: ToSql + 'static;
+
+    const VERSION_COLUMN: &'static str;
 }
 
 pub trait SchemaHas<Row: Model> {}
 
 pub trait Schema {
     fn create(connection: &mut Connection) -> Result<(), Error>;
+
+    /// Checks that every table and column exists, for connections that cannot create them.
+    fn verify(connection: &Connection) -> Result<(), Error>;
 }
 
 impl Schema for () {
     fn create(_connection: &mut Connection) -> Result<(), Error> {
         Ok(())
     }
+
+    fn verify(_connection: &Connection) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 impl<Head: Model, Tail: Schema> Schema for (Head, Tail) {
     fn create(connection: &mut Connection) -> Result<(), Error> {
         let columns = Head::COLUMNS.iter()
             .map(|(name, type_)| format!("{} {}", name, type_))
+            .chain(Head::FOREIGN_KEYS.iter().map(|(column, table, parent)| format!("FOREIGN KEY({}) REFERENCES {}({})", column, table, parent)))
             .collect::<Vec<_>>()
             .join(", ");
 
@@ -41,6 +91,25 @@ impl<Head: Model, Tail: Schema> Schema for (Head, Tail) {
 
         Tail::create(connection)
     }
+
+    fn verify(connection: &Connection) -> Result<(), Error> {
+        let mut stmt = connection.prepare("SELECT name FROM pragma_table_info(?1)")
+            .map_err(|err| Error::SchemaError(Box::new(err)))?;
+
+        let existing = stmt.query_map([Head::NAME], |row| row.get::<_, String>(0))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|err| Error::SchemaError(Box::new(err)))?;
+
+        if existing.is_empty() {
+            return Err(Error::SchemaError(format!("table {} does not exist", Head::NAME).into()));
+        }
+
+        if let Some((name, _)) = Head::COLUMNS.iter().find(|(name, _)| !existing.iter().any(|column| column.eq_ignore_ascii_case(name))) {
+            return Err(Error::SchemaError(format!("table {} has no column {}", Head::NAME, name).into()));
+        }
+
+        Tail::verify(connection)
+    }
 }
 
 
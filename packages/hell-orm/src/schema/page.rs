@@ -0,0 +1,135 @@
+use crate::error::Error;
+
+use rusqlite::types::Value;
+
+use std::str::FromStr;
+
+
+/// The version of the cursor encoding, stored as the first byte of every cursor.
+const VERSION: u8 = 1;
+
+/// The position after the last row of a page, passed back to `paginate_after` to fetch the next one.
+///
+/// A cursor round trips through its `Display` and `FromStr` implementations as one opaque hex string,
+/// so it can be handed to clients. The encoding is versioned and may change, clients should only pass
+/// cursors back as they received them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor {
+    pub(crate) values: Vec<Value>,
+}
+
+impl Cursor {
+    /// The version byte followed by every value, each tagged with its type.
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = vec![VERSION];
+
+        for value in self.values.iter() {
+            match value {
+                Value::Null => bytes.push(0),
+                Value::Integer(integer) => {
+                    bytes.push(1);
+                    bytes.extend(integer.to_be_bytes());
+                },
+                Value::Real(real) => {
+                    bytes.push(2);
+                    bytes.extend(real.to_bits().to_be_bytes());
+                },
+                Value::Text(text) => {
+                    bytes.push(3);
+                    bytes.extend((text.len() as u32).to_be_bytes());
+                    bytes.extend(text.as_bytes());
+                },
+                Value::Blob(blob) => {
+                    bytes.push(4);
+                    bytes.extend((blob.len() as u32).to_be_bytes());
+                    bytes.extend(blob);
+                },
+            }
+        }
+
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Cursor> {
+        let (&VERSION, mut rest) = bytes.split_first()? else {
+            return None;
+        };
+
+        let mut values = Vec::new();
+
+        while let Some((tag, tail)) = rest.split_first() {
+            let (value, tail) = match tag {
+                0 => (Value::Null, tail),
+                1 => tail.split_first_chunk().map(|(bytes, tail)| (Value::Integer(i64::from_be_bytes(*bytes)), tail))?,
+                2 => tail.split_first_chunk().map(|(bytes, tail)| (Value::Real(f64::from_bits(u64::from_be_bytes(*bytes))), tail))?,
+                3 | 4 => {
+                    let (length, tail) = tail.split_first_chunk()?;
+                    let (data, tail) = tail.split_at_checked(u32::from_be_bytes(*length) as usize)?;
+
+                    let value = if *tag == 3 {
+                        Value::Text(String::from_utf8(data.to_vec()).ok()?)
+                    } else {
+                        Value::Blob(data.to_vec())
+                    };
+
+                    (value, tail)
+                },
+                _ => return None,
+            };
+
+            values.push(value);
+            rest = tail;
+        }
+
+        Some(Cursor { values })
+    }
+}
+
+impl std::fmt::Display for Cursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        f.write_str(&hex(&self.encode()))
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = Error;
+
+    fn from_str(cursor: &str) -> Result<Cursor, Error> {
+        unhex(cursor)
+            .and_then(|bytes| Cursor::decode(&bytes))
+            .ok_or(Error::InvalidCursor)
+    }
+}
+
+/// Whether a cursor value can be compared with a column declared as `declared`, NULL never can.
+pub(crate) fn matches_column(value: &Value, declared: &str) -> bool {
+    match value {
+        Value::Integer(_) => declared.starts_with("INTEGER"),
+        Value::Text(_) => declared.starts_with("TEXT"),
+        Value::Null | Value::Real(_) | Value::Blob(_) => false,
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn unhex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| hex.get(index..index + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+        .collect()
+}
+
+/// One page of results from `paginate_after`.
+pub struct Page<T> {
+    pub items: Vec<T>,
+
+    /// The cursor of the following page, `None` when this is the last one.
+    pub next: Option<Cursor>,
+}
+
@@ -1,6 +1,23 @@
-use crate::schema::Model;
+use crate::schema::update::UpdateBuilder;
+use crate::schema::relation::{BelongsTo, IncludeBuilder};
+use crate::schema::join::{On, JoinBuilder, Inner, Left};
+use crate::schema::recursive::RecursiveBuilder;
+use crate::schema::condition::{Condition, Expr};
+use crate::schema::column::Order;
+use crate::schema::page::{Cursor, Page, matches_column};
+use crate::schema::aggregate::{Aggregate, GroupBuilder, Numeric, count};
+use crate::schema::column::Column;
+use crate::schema::select::{Projection, Selectable, Selection};
+use crate::schema::subquery::{Fragment, Subquery, With};
+use crate::schema::timestamp::Timestamp;
+use crate::schema::{Model, PrimaryKey, SchemaHas};
+use crate::error::Error;
 
-use rusqlite::Connection;
+use rusqlite::types::{FromSql, Value};
+use rusqlite::{CachedStatement, Connection, Statement, ToSql};
+
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
 
 
 pub enum Where<T> {
@@ -8,30 +25,648 @@ pub enum Where<T> {
     NotEqual(T),
     LessThan(T),
     GreaterThan(T),
+    LessOrEqual(T),
+    GreaterOrEqual(T),
+}
+
+impl<T> Where<T> {
+    pub fn operator(&self) -> &'static str {
+        match self {
+            Where::Equal(_) => "=",
+            Where::NotEqual(_) => "!=",
+            Where::LessThan(_) => "<",
+            Where::GreaterThan(_) => ">",
+            Where::LessOrEqual(_) => "<=",
+            Where::GreaterOrEqual(_) => ">=",
+        }
+    }
+
+    pub fn into_value(self) -> T {
+        match self {
+            Where::Equal(value) | Where::NotEqual(value) | Where::LessThan(value) | Where::GreaterThan(value) | Where::LessOrEqual(value) | Where::GreaterOrEqual(value) => value,
+        }
+    }
+}
+
+enum Deleted {
+    Excluded,
+    Included,
+    Only,
+}
+
+pub trait Query {
+    type Builder<'a, S>;
+
+    fn builder<'a, S>(connection: &'a mut Connection) -> Self::Builder<'a, S>;
 }
 
-pub trait Query<'a, T> {
-    type Builder;
+/// The qualified column list of `Row`, in the order `FromRow::from_row_at` decodes it.
+pub fn select_list<Row: Model>() -> String {
+    select_list_as::<Row>(Row::NAME)
+}
+
+/// The column list of `Row` qualified with `table`, for a table selected under an alias.
+pub(crate) fn select_list_as<Row: Model>(table: &str) -> String {
+    Row::COLUMNS.iter()
+        .map(|(name, _)| format!("{}.{}", table, name))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// A statement prepared through the connection's statement cache, or on its own.
+pub(crate) enum Prepared<'c> {
+    Cached(CachedStatement<'c>),
+    Uncached(Statement<'c>),
+}
+
+impl<'c> Deref for Prepared<'c> {
+    type Target = Statement<'c>;
+
+    fn deref(&self) -> &Statement<'c> {
+        match self {
+            Prepared::Cached(stmt) => stmt,
+            Prepared::Uncached(stmt) => stmt,
+        }
+    }
+}
+
+impl<'c> DerefMut for Prepared<'c> {
+    fn deref_mut(&mut self) -> &mut Statement<'c> {
+        match self {
+            Prepared::Cached(stmt) => stmt,
+            Prepared::Uncached(stmt) => stmt,
+        }
+    }
+}
+
+/// Prepares `sql`, through the cache unless it is `variable`.
+///
+/// SQL rendering an `IN` list differs for every list length, so caching it would only push
+/// the statements that do get reused out of the cache.
+pub(crate) fn prepare<'c>(connection: &'c Connection, sql: &str, variable: bool) -> Result<Prepared<'c>, Error> {
+    let prepared = if variable {
+        connection.prepare(sql).map(Prepared::Uncached)
+    } else {
+        connection.prepare_cached(sql).map(Prepared::Cached)
+    };
+
+    prepared.map_err(|err| Error::StatementError(Box::new(err)))
+}
+
+/// The clauses of a select that don't depend on the connection, shared by queries and subqueries.
+pub(crate) struct Clauses<Row> {
+    pub(crate) filters: Vec<Condition<Row>>,
+    pub(crate) order: Vec<Order<Row>>,
+    pub(crate) limit: Option<usize>,
+    pub(crate) offset: Option<usize>,
+    pub(crate) distinct: bool,
+    /// Common table expressions added with `with`, as `(name, select)`.
+    pub(crate) tables: Vec<(&'static str, Box<dyn Fragment>)>,
+    deleted: Deleted,
+}
+
+impl<Row: Model> Clauses<Row> {
+    fn new() -> Clauses<Row> {
+        Clauses {
+            filters: Vec::new(),
+            order: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+            tables: Vec::new(),
+            deleted: Deleted::Excluded,
+        }
+    }
+
+    /// Renders the where clause, numbering its placeholders after the `index` already bound parameters.
+    pub(crate) fn where_clause(&self, index: &mut usize) -> String {
+        let mut conditions = self.filters.iter()
+            .map(|condition| condition.render(index))
+            .collect::<Vec<String>>();
+
+        conditions.extend(self.deleted_condition());
+
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        }
+    }
+
+    /// Renders every common table expression as `name AS (select)`.
+    ///
+    /// Their placeholders follow the filters, so this renders after `where_clause` even though
+    /// the `WITH` ends up first in the statement.
+    pub(crate) fn tables(&self, index: &mut usize) -> Vec<String> {
+        self.tables.iter()
+            .map(|(name, select)| format!("{} AS ({})", name, select.render(index)))
+            .collect()
+    }
+
+    /// Renders the `WITH` prefix of the statement, empty without common table expressions.
+    pub(crate) fn with_clause(&self, index: &mut usize) -> String {
+        let tables = self.tables(index);
+
+        if tables.is_empty() {
+            String::new()
+        } else {
+            format!("WITH {} ", tables.join(", "))
+        }
+    }
+
+    /// The condition selecting soft deleted rows or not, `None` when every row is included.
+    pub(crate) fn deleted_condition(&self) -> Option<String> {
+        match self.deleted {
+            Deleted::Excluded if Row::SOFT_DELETE => Some(format!("{}.deleted_at IS NULL", Row::NAME)),
+            Deleted::Only if Row::SOFT_DELETE => Some(format!("{}.deleted_at IS NOT NULL", Row::NAME)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn order_clause(&self) -> String {
+        let mut sql = String::new();
+
+        if !self.order.is_empty() {
+            let order = self.order.iter()
+                .map(|order| order.render())
+                .collect::<Vec<String>>()
+                .join(", ");
 
-    fn builder(connection: &'a mut Connection) -> Self::Builder;
+            sql.push_str(&format!(" ORDER BY {}", order));
+        }
+
+        // SQLite reads LIMIT and OFFSET as signed 64 bit integers
+        let clamp = |value: usize| value.min(i64::MAX as usize);
+
+        match (self.limit.map(clamp), self.offset.map(clamp)) {
+            (Some(limit), Some(offset)) => sql.push_str(&format!(" LIMIT {} OFFSET {}", limit, offset)),
+            (Some(limit), None) => sql.push_str(&format!(" LIMIT {}", limit)),
+            (None, Some(offset)) => sql.push_str(&format!(" LIMIT -1 OFFSET {}", offset)),
+            (None, None) => {},
+        }
+
+        sql
+    }
+
+    pub(crate) fn select_keyword(&self) -> &'static str {
+        if self.distinct { "SELECT DISTINCT" } else { "SELECT" }
+    }
+
+    /// The parameters of the filters followed by those of the common table expressions.
+    pub(crate) fn params(&self) -> impl Iterator<Item = &dyn ToSql> {
+        self.filter_params()
+            .chain(self.tables.iter().flat_map(|(_, select)| select.params()))
+    }
+
+    fn filter_params(&self) -> impl Iterator<Item = &dyn ToSql> {
+        self.filters.iter().flat_map(|condition| condition.params())
+    }
+
+    /// Whether any filter or common table expression renders differently depending on the number of bound values.
+    pub(crate) fn is_variable(&self) -> bool {
+        self.filters.iter().any(Condition::is_variable) || self.tables.iter().any(|(_, select)| select.is_variable())
+    }
 }
 
-pub struct QueryBuilder<'a> {
+pub struct QueryBuilder<'a, Row, S> {
     pub connection: &'a mut Connection,
     pub table_name: &'a str,
+    clauses: Clauses<Row>,
+    _marker: PhantomData<(Row, S)>,
 }
 
-impl<'a> QueryBuilder<'a> {
-    pub fn new(connection: &'a mut Connection, table_name: &'a str) -> QueryBuilder<'a> {
+impl<'a, Row: Model, S> QueryBuilder<'a, Row, S> {
+    pub fn new(connection: &'a mut Connection, table_name: &'a str) -> QueryBuilder<'a, Row, S> {
         QueryBuilder {
             connection,
             table_name,
+            clauses: Clauses::new(),
+            _marker: PhantomData,
         }
     }
 
-    // TODO: we need a get() and all() function for retrieving single values and multiple values
-    //fn get<T: Model<'a>>(self) -> &'a T {
-    //}
-}
+    pub fn filter(mut self, condition: Condition<Row>) -> QueryBuilder<'a, Row, S> {
+        self.clauses.filters.push(condition);
+        self
+    }
+
+    /// Sorts the results by `order`, after any earlier sort keys.
+    pub fn order_by(mut self, order: Order<Row>) -> QueryBuilder<'a, Row, S> {
+        self.clauses.order.push(order);
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> QueryBuilder<'a, Row, S> {
+        self.clauses.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> QueryBuilder<'a, Row, S> {
+        self.clauses.offset = Some(offset);
+        self
+    }
+
+    /// Removes duplicate results, mostly useful together with `select`.
+    pub fn distinct(mut self) -> QueryBuilder<'a, Row, S> {
+        self.clauses.distinct = true;
+        self
+    }
+
+    /// Adds the common table expression `with` ahead of the query, see `Subquery::named`.
+    pub fn with<T>(mut self, with: With<T>) -> QueryBuilder<'a, Row, S> {
+        self.clauses.tables.push((with.name, with.select));
+        self
+    }
+
+    /// Includes soft deleted rows in the query.
+    pub fn with_deleted(mut self) -> QueryBuilder<'a, Row, S> {
+        self.clauses.deleted = Deleted::Included;
+        self
+    }
+
+    /// Restricts the query to soft deleted rows.
+    pub fn only_deleted(mut self) -> QueryBuilder<'a, Row, S> {
+        self.clauses.deleted = Deleted::Only;
+        self
+    }
+
+    /// Renders the where clause, numbering the placeholders after `offset` already bound parameters.
+    pub fn where_clause(&self, offset: usize) -> String {
+        let mut index = offset;
+
+        self.clauses.where_clause(&mut index)
+    }
+
+    /// Renders the `WITH` prefix of the statement, numbering its placeholders after the where clause
+    /// and the `offset` parameters bound before it.
+    pub fn with_clause(&self, offset: usize) -> String {
+        let mut index = offset + self.clauses.filter_params().count();
+
+        self.clauses.with_clause(&mut index)
+    }
+
+    /// Renders the where clause of an `UPDATE` or `DELETE`, which SQLite can't order or limit itself.
+    ///
+    /// With `order_by`, `limit` or `offset` set the rows are picked by a subquery on their `rowid`,
+    /// so only the rows a select would return are changed.
+    pub fn target_clause(&self, offset: usize) -> String {
+        let clauses = &self.clauses;
+
+        if clauses.order.is_empty() && clauses.limit.is_none() && clauses.offset.is_none() {
+            return self.where_clause(offset);
+        }
+
+        format!(" WHERE rowid IN (SELECT rowid FROM {}{}{})", self.table_name, self.where_clause(offset), self.order_clause())
+    }
+
+    /// Renders the `ORDER BY`, `LIMIT` and `OFFSET` clauses that follow the where clause.
+    pub fn order_clause(&self) -> String {
+        self.clauses.order_clause()
+    }
+
+    /// `SELECT`, or `SELECT DISTINCT` when `distinct` was called.
+    pub fn select_keyword(&self) -> &'static str {
+        self.clauses.select_keyword()
+    }
+
+    pub fn params(&self) -> impl Iterator<Item = &dyn ToSql> {
+        self.clauses.params()
+    }
+
+    pub(crate) fn select_rows(&self) -> Result<Vec<Row>, Error> {
+        self.select_with(&select_list::<Row>(), |row| Row::from_row_at(row, 0))
+    }
+
+    /// Whether the SQL of the query changes with the number of bound values, see `prepare`.
+    pub(crate) fn is_variable(&self) -> bool {
+        self.clauses.is_variable()
+    }
 
+    /// The full select of `columns` with every clause of the query, bound with `params`.
+    fn select_sql(&self, columns: &str) -> String {
+        format!("{}{} {} FROM {}{}{}", self.with_clause(0), self.select_keyword(), columns, self.table_name, self.where_clause(0), self.order_clause())
+    }
+
+    fn select_with<T>(&self, columns: &str, f: impl FnMut(&rusqlite::Row) -> Result<T, rusqlite::Error>) -> Result<Vec<T>, Error> {
+        let sql = self.select_sql(columns);
+
+        let mut stmt = prepare(self.connection, sql.as_str(), self.is_variable())?;
+
+        stmt.query_map(rusqlite::params_from_iter(self.params()), f)
+            .and_then(|rows| rows.collect())
+            .map_err(|err| Error::QueryError(Box::new(err)))
+    }
+
+    /// Caps `limit` at one row, keeping a `limit(0)` that matches nothing.
+    pub(crate) fn single(mut self) -> QueryBuilder<'a, Row, S> {
+        self.clauses.limit = Some(self.clauses.limit.map_or(1, |limit| limit.min(1)));
+        self
+    }
+
+    /// The first matching row, after `offset` if one is set.
+    pub fn get(self) -> Result<Option<Row>, Error> {
+        self.single().select_rows().map(|rows| rows.into_iter().next())
+    }
+
+    pub fn all(self) -> Result<Vec<Row>, Error> {
+        self.select_rows()
+    }
+
+    /// Whether any row matches, without loading it. An `offset` past the last match makes it `false`.
+    pub fn exists(self) -> Result<bool, Error> {
+        let sql = format!("{}SELECT EXISTS(SELECT 1 FROM {}{}{})", self.with_clause(0), self.table_name, self.where_clause(0), self.order_clause());
+
+        let mut stmt = prepare(self.connection, sql.as_str(), self.is_variable())?;
+
+        stmt.query_row(rusqlite::params_from_iter(self.params()), |row| row.get(0))
+            .map_err(|err| Error::QueryError(Box::new(err)))
+    }
+
+    /// The first matching row in `order_by` order, ties and unordered queries going to the lowest primary key.
+    pub fn first(mut self) -> Result<Option<Row>, Error>
+    where
+        Row: PrimaryKey
+    {
+        if self.clauses.order.iter().all(|order| order.name != Row::PRIMARY_KEY) {
+            self.clauses.order.push(Order::new(Row::PRIMARY_KEY, "ASC"));
+        }
+
+        self.get()
+    }
+
+    /// The last matching row in `order_by` order, ties and unordered queries going to the highest primary key.
+    ///
+    /// With `limit` or `offset` set it is the last of the rows a select would return, so
+    /// `offset(5).last()` is still the last row rather than the sixth one from the end.
+    pub fn last(mut self) -> Result<Option<Row>, Error>
+    where
+        Row: PrimaryKey
+    {
+        if self.clauses.order.iter().all(|order| order.name != Row::PRIMARY_KEY) {
+            self.clauses.order.push(Order::new(Row::PRIMARY_KEY, "ASC"));
+        }
+
+        if self.clauses.limit.is_none() && self.clauses.offset.is_none() {
+            self.clauses.order = self.clauses.order.iter().map(Order::reversed).collect();
+
+            return self.get();
+        }
+
+        let reversed = self.clauses.order.iter()
+            .map(|order| order.reversed().render())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let sql = format!(
+            "{with}SELECT {columns} FROM ({select} * FROM {table}{filter}{order}) AS {table} ORDER BY {reversed} LIMIT 1",
+            with = self.with_clause(0),
+            columns = select_list::<Row>(),
+            select = self.select_keyword(),
+            table = self.table_name,
+            filter = self.where_clause(0),
+            order = self.order_clause(),
+        );
+
+        let mut stmt = prepare(self.connection, sql.as_str(), self.is_variable())?;
+
+        stmt.query_map(rusqlite::params_from_iter(self.params()), |row| Row::from_row_at(row, 0))
+            .and_then(|mut rows| rows.next().transpose())
+            .map_err(|err| Error::QueryError(Box::new(err)))
+    }
+
+    /// The matching row whose primary key is `id`.
+    pub fn get_by_id(self, id: Row::Key) -> Result<Option<Row>, Error>
+    where
+        Row: PrimaryKey
+    {
+        self.filter(Column::<Row, Row::Key>::new(Row::PRIMARY_KEY).eq(id)).get()
+    }
+
+    /// Calls `f` with every matching row as it is read, without collecting the results.
+    pub fn for_each(self, mut f: impl FnMut(Row)) -> Result<(), Error> {
+        self.try_for_each(|row| {
+            f(row);
+
+            Ok::<(), Error>(())
+        })
+    }
+
+    /// Calls `f` with every matching row as it is read, stopping at the first error.
+    pub fn try_for_each<E: From<Error>>(self, mut f: impl FnMut(Row) -> Result<(), E>) -> Result<(), E> {
+        let sql = self.select_sql(&select_list::<Row>());
+
+        let mut stmt = prepare(self.connection, sql.as_str(), self.is_variable())?;
+
+        let mut rows = stmt.query(rusqlite::params_from_iter(self.params()))
+            .map_err(|err| Error::QueryError(Box::new(err)))?;
+
+        while let Some(row) = rows.next().map_err(|err| Error::QueryError(Box::new(err)))? {
+            f(Row::from_row_at(row, 0).map_err(|err| Error::QueryError(Box::new(err)))?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Loads only the columns in `selection`, such as a tuple of columns `(User::ID_COL, User::NAME_COL)`.
+    pub fn select<Sel: Selection<Row>>(self, selection: Sel) -> Result<Vec<Sel::Output>, Error> {
+        self.select_with(&selection.columns().join(", "), |row| Sel::decode(row, 0))
+    }
+
+    /// Turns the query into a subquery selecting `selection`, to use in the filters of another query.
+    pub fn subquery<Sel: Selectable<Row>>(self, selection: Sel) -> Subquery<Sel::Output>
+    where
+        Row: 'static
+    {
+        Subquery::new(selection.sql(), self.clauses)
+    }
+
+    /// Loads only the columns of the projection `P`.
+    pub fn select_as<P: Projection<Row>>(self) -> Result<Vec<P>, Error> {
+        let columns = P::COLUMNS.iter()
+            .map(|name| format!("{}.{}", self.table_name, name))
+            .collect::<Vec<String>>();
+
+        self.select_with(&columns.join(", "), |row| P::from_row(row, 0))
+    }
+
+    /// Fetches the `page_size` rows following `cursor`, or the first page when `cursor` is `None`.
+    ///
+    /// Rows are sorted by the `order_by` keys with the primary key as a tie breaker, and every key
+    /// must sort in the same direction. Sort keys must not be NULL, a page whose last row has a NULL
+    /// key fails with `Error::InvalidPageOrder` rather than returning a cursor that skips rows.
+    pub fn paginate_after(mut self, cursor: Option<&Cursor>, page_size: usize) -> Result<Page<Row>, Error>
+    where
+        Row: PrimaryKey
+    {
+        let direction = self.clauses.order.first().map(|order| order.direction).unwrap_or("ASC");
+
+        if self.clauses.order.iter().any(|order| order.direction != direction) {
+            return Err(Error::InvalidPageOrder);
+        }
+
+        if self.clauses.order.iter().all(|order| order.name != Row::PRIMARY_KEY) {
+            self.clauses.order.push(Order::new(Row::PRIMARY_KEY, direction));
+        }
+
+        let keys = self.clauses.order.iter()
+            .map(|order| Row::COLUMNS.iter().position(|(name, _)| *name == order.name).unwrap_or_default())
+            .collect::<Vec<usize>>();
+
+        if let Some(cursor) = cursor {
+            let valid = cursor.values.len() == keys.len() && cursor.values.iter()
+                .zip(keys.iter())
+                .all(|(value, index)| matches_column(value, Row::COLUMNS[*index].1));
+
+            if !valid {
+                return Err(Error::InvalidCursor);
+            }
+
+            let columns = self.clauses.order.iter()
+                .map(|order| format!("{}.{}", Row::NAME, order.name))
+                .collect();
+
+            let values = cursor.values.iter()
+                .map(|value| Box::new(value.clone()) as Box<dyn ToSql>)
+                .collect();
+
+            self.clauses.filters.push(Condition::new(Expr::Tuple(columns, if direction == "ASC" { ">" } else { "<" }, values)));
+        }
+
+        self.clauses.limit = Some(page_size.saturating_add(1));
+        self.clauses.offset = None;
+
+        let mut rows = self.select_with(&select_list::<Row>(), |row| {
+            let values = keys.iter()
+                .map(|index| row.get::<_, Value>(*index))
+                .collect::<Result<Vec<Value>, rusqlite::Error>>()?;
+
+            Ok((Row::from_row_at(row, 0)?, values))
+        })?;
+
+        let next = if rows.len() > page_size {
+            rows.truncate(page_size);
+
+            match rows.last() {
+                Some((_, values)) if values.contains(&Value::Null) => return Err(Error::InvalidPageOrder),
+                Some((_, values)) => Some(Cursor { values: values.clone() }),
+                None => None,
+            }
+        } else {
+            None
+        };
+
+        Ok(Page {
+            items: rows.into_iter().map(|(row, _)| row).collect(),
+            next,
+        })
+    }
+
+    /// Evaluates `aggregate` over the matching rows.
+    ///
+    /// With `limit`, `offset` or `distinct` set it runs over the rows a select would return,
+    /// so `limit(10).sum(...)` adds up the first ten rows in the query's order.
+    pub fn aggregate<T: FromSql>(self, aggregate: Aggregate<Row, T>) -> Result<T, Error> {
+        let clauses = &self.clauses;
+
+        let sql = if clauses.limit.is_none() && clauses.offset.is_none() && !clauses.distinct {
+            format!("{}SELECT {} FROM {}{}", self.with_clause(0), aggregate.sql, self.table_name, self.where_clause(0))
+        } else {
+            format!(
+                "{with}SELECT {aggregate} FROM ({select} * FROM {table}{filter}{order}) AS {table}",
+                with = self.with_clause(0),
+                aggregate = aggregate.sql,
+                select = self.select_keyword(),
+                table = self.table_name,
+                filter = self.where_clause(0),
+                order = self.order_clause(),
+            )
+        };
+
+        let mut stmt = prepare(self.connection, sql.as_str(), self.is_variable())?;
+
+        stmt.query_row(rusqlite::params_from_iter(self.params()), |row| row.get(0))
+            .map_err(|err| Error::QueryError(Box::new(err)))
+    }
+
+    pub fn count(self) -> Result<i64, Error> {
+        self.aggregate(count())
+    }
+
+    pub fn sum<T: FromSql + Numeric>(self, column: Column<Row, T>) -> Result<Option<T>, Error> {
+        self.aggregate(column.sum())
+    }
+
+    pub fn avg<T: Numeric>(self, column: Column<Row, T>) -> Result<Option<f64>, Error> {
+        self.aggregate(column.avg())
+    }
+
+    pub fn min<T: FromSql>(self, column: Column<Row, T>) -> Result<Option<T>, Error> {
+        self.aggregate(column.min())
+    }
+
+    pub fn max<T: FromSql>(self, column: Column<Row, T>) -> Result<Option<T>, Error> {
+        self.aggregate(column.max())
+    }
+
+    /// Groups the matching rows by `column`, to select aggregates per group.
+    pub fn group_by<T>(self, column: Column<Row, T>) -> GroupBuilder<'a, Row, S> {
+        GroupBuilder::new(self, column.qualified())
+    }
+
+    /// Loads the `Child` rows of every result in one batched query.
+    pub fn include<Child: BelongsTo<Row>>(self) -> IncludeBuilder<'a, Row, Child, S>
+    where
+        Row: PrimaryKey,
+        S: SchemaHas<Child>
+    {
+        IncludeBuilder::new(self)
+    }
+
+    /// Joins every result with the `Other` row matching `on`, skipping results without one.
+    pub fn join<Other: Model>(self, on: On<Row, Other>) -> JoinBuilder<'a, Row, Other, S, Inner>
+    where
+        S: SchemaHas<Other>
+    {
+        JoinBuilder::new(self, on)
+    }
+
+    /// Joins every result with the `Other` row matching `on`, if there is one.
+    pub fn left_join<Other: Model>(self, on: On<Row, Other>) -> JoinBuilder<'a, Row, Other, S, Left>
+    where
+        S: SchemaHas<Other>
+    {
+        JoinBuilder::new(self, on)
+    }
+
+    /// Walks a tree stored in `Row`, starting from the matching rows and following `step` from every row
+    /// found to the next, such as `on!(Comment::parent == Comment::id)` to descend into replies.
+    pub fn recursive(self, step: On<Row, Row>) -> RecursiveBuilder<'a, Row, S> {
+        RecursiveBuilder::new(self.connection, self.clauses, step)
+    }
+
+    /// Starts an update of the matching rows, picked with `order_by`, `limit` and `offset` as they would be for a select.
+    pub fn update(self) -> UpdateBuilder<'a, Row, S> {
+        UpdateBuilder::new(self)
+    }
+
+    /// Deletes the matching rows, or marks them as deleted if the model uses `#[soft_delete]`.
+    ///
+    /// `order_by`, `limit` and `offset` pick the rows to delete as they would for a select.
+    pub fn delete(self) -> Result<usize, Error> {
+        let deleted_at = <i64 as Timestamp>::now();
+
+        let (sql, params) = if Row::SOFT_DELETE {
+            let sql = format!("{}UPDATE {} SET deleted_at = ?1{}", self.with_clause(1), self.table_name, self.target_clause(1));
+
+            (sql, std::iter::once(&deleted_at as &dyn ToSql).chain(self.params()).collect::<Vec<_>>())
+        } else {
+            (format!("{}DELETE FROM {}{}", self.with_clause(0), self.table_name, self.target_clause(0)), self.params().collect::<Vec<_>>())
+        };
+
+        let mut stmt = prepare(self.connection, sql.as_str(), self.is_variable())?;
+
+        stmt.execute(params.as_slice())
+            .map_err(|err| Error::DeleteError(Box::new(err)))
+    }
+}
 
@@ -0,0 +1,101 @@
+use crate::schema::query::{Clauses, prepare, select_list};
+use crate::schema::condition::placeholder;
+use crate::schema::join::On;
+use crate::schema::Model;
+use crate::error::Error;
+
+use rusqlite::{Connection, ToSql};
+
+use std::marker::PhantomData;
+
+
+/// A `WITH RECURSIVE` query walking a tree of `Row`s, built with `recursive`.
+///
+/// The walk follows every path it finds, so trees containing cycles need a `max_depth`.
+///
+/// Common table expressions added to the query with `with` are defined ahead of the walk, in the
+/// same `WITH RECURSIVE` clause.
+pub struct RecursiveBuilder<'a, Row, S> {
+    connection: &'a mut Connection,
+    clauses: Clauses<Row>,
+    step: On<Row, Row>,
+    max_depth: Option<usize>,
+    _marker: PhantomData<S>,
+}
+
+impl<'a, Row: Model, S> RecursiveBuilder<'a, Row, S> {
+    pub(crate) fn new(connection: &'a mut Connection, clauses: Clauses<Row>, step: On<Row, Row>) -> RecursiveBuilder<'a, Row, S> {
+        RecursiveBuilder {
+            connection,
+            clauses,
+            step,
+            max_depth: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Stops the walk `depth` steps away from the starting rows.
+    pub fn max_depth(mut self, depth: usize) -> RecursiveBuilder<'a, Row, S> {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Every row found together with its distance from the starting rows, which have a depth of zero.
+    pub fn all_with_depth(self) -> Result<Vec<(Row, usize)>, Error> {
+        let mut index = 0;
+
+        let columns = Row::COLUMNS.iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<&str>>()
+            .join(", ");
+
+        let anchor = self.clauses.where_clause(&mut index);
+
+        let mut tables = self.clauses.tables(&mut index);
+
+        let mut conditions = Vec::from_iter(self.clauses.deleted_condition());
+
+        if self.max_depth.is_some() {
+            conditions.push(format!("__tree.__depth < {}", placeholder(&mut index)));
+        }
+
+        let step = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        };
+
+        tables.push(format!(
+            "__tree({columns}, __depth) AS ({select} {list}, 0 FROM {table}{anchor} UNION ALL SELECT {list}, __tree.__depth + 1 FROM {table} INNER JOIN __tree ON {table}.{left} = __tree.{right}{step})",
+            select = self.clauses.select_keyword(),
+            list = select_list::<Row>(),
+            table = Row::NAME,
+            left = self.step.left,
+            right = self.step.right,
+        ));
+
+        let sql = format!(
+            "WITH RECURSIVE {tables} {select} {list}, {table}.__depth FROM __tree AS {table}{order}",
+            tables = tables.join(", "),
+            select = self.clauses.select_keyword(),
+            list = select_list::<Row>(),
+            table = Row::NAME,
+            order = self.clauses.order_clause(),
+        );
+
+        let params = self.clauses.params()
+            .chain(self.max_depth.as_ref().map(|depth| depth as &dyn ToSql));
+
+        let mut stmt = prepare(self.connection, sql.as_str(), self.clauses.is_variable())?;
+
+        stmt.query_map(rusqlite::params_from_iter(params), |row| Ok((Row::from_row_at(row, 0)?, row.get(Row::COLUMNS.len())?)))
+            .and_then(|rows| rows.collect())
+            .map_err(|err| Error::QueryError(Box::new(err)))
+    }
+
+    /// Every row found, starting rows included.
+    pub fn all(self) -> Result<Vec<Row>, Error> {
+        self.all_with_depth().map(|rows| rows.into_iter().map(|(row, _)| row).collect())
+    }
+}
+
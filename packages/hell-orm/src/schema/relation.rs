@@ -0,0 +1,70 @@
+use crate::schema::query::QueryBuilder;
+use crate::schema::column::Column;
+use crate::schema::{Model, PrimaryKey};
+use crate::error::Error;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+
+/// The most parent keys bound in one children query, well below SQLite's limit on host parameters.
+const KEYS_PER_QUERY: usize = 500;
+
+/// Declared by `#[belongs_to(Parent, key = "...")]` on the child model.
+pub trait BelongsTo<Parent: PrimaryKey>: Model {
+    const FOREIGN_KEY: &'static str;
+
+    /// The key of the parent, `None` when an `Option` key is NULL and the row has no parent.
+    fn foreign_key(&self) -> Option<&Parent::Key>;
+}
+
+pub struct IncludeBuilder<'a, Row, Child, S> {
+    query: QueryBuilder<'a, Row, S>,
+    _marker: PhantomData<Child>,
+}
+
+impl<'a, Row: PrimaryKey, Child: BelongsTo<Row>, S> IncludeBuilder<'a, Row, Child, S> {
+    pub fn new(query: QueryBuilder<'a, Row, S>) -> IncludeBuilder<'a, Row, Child, S> {
+        IncludeBuilder {
+            query,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn all(self) -> Result<Vec<(Row, Vec<Child>)>, Error>
+    where
+        Row::Key: Hash + Eq
+    {
+        let rows = self.query.select_rows()?;
+
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let keys = rows.iter()
+            .map(|row| row.primary_key().clone())
+            .collect::<Vec<Row::Key>>();
+
+        let mut children: HashMap<Row::Key, Vec<Child>> = HashMap::new();
+
+        for keys in keys.chunks(KEYS_PER_QUERY) {
+            let column = Column::<Child, Row::Key>::new(Child::FOREIGN_KEY);
+
+            for child in QueryBuilder::<Child, S>::new(self.query.connection, Child::NAME).filter(column.in_(keys.to_vec())).all()? {
+                if let Some(key) = child.foreign_key() {
+                    children.entry(key.clone()).or_default().push(child);
+                }
+            }
+        }
+
+        Ok(rows.into_iter()
+            .map(|row| {
+                let children = children.remove(row.primary_key()).unwrap_or_default();
+
+                (row, children)
+            })
+            .collect())
+    }
+}
+
@@ -0,0 +1,52 @@
+use rusqlite::types::FromSql;
+use rusqlite::Row;
+
+
+/// Types that can be read from a result row of any shape, usually derived with `#[derive(FromRow)]`.
+///
+/// Fields are read by column name, or by position with `#[row(index = N)]`, nested
+/// `FromRow` types are read from the same row with `#[row(flatten)]`.
+pub trait FromRow: Sized {
+    /// Decodes the value with indexed columns counted from `offset`, named columns ignore it.
+    fn from_row_at(row: &Row, offset: usize) -> Result<Self, rusqlite::Error>;
+
+    fn from_row(row: &Row) -> Result<Self, rusqlite::Error> {
+        Self::from_row_at(row, 0)
+    }
+}
+
+/// Names the field being decoded in a column error, used by the derived `FromRow` impls.
+#[doc(hidden)]
+pub fn field_error(row: &Row, err: rusqlite::Error, field: &str) -> rusqlite::Error {
+    match err {
+        rusqlite::Error::InvalidColumnName(name) => rusqlite::Error::InvalidColumnName(format!("{} (required by `{}`, missing from the result)", name, field)),
+        rusqlite::Error::InvalidColumnIndex(index) => {
+            let count = row.as_ref().column_count();
+
+            rusqlite::Error::InvalidColumnName(format!("#{} (required by `{}`, the result has {} columns)", index, field, count))
+        },
+        rusqlite::Error::InvalidColumnType(index, name, ty) => rusqlite::Error::InvalidColumnType(index, format!("{} (decoding `{}`)", name, field), ty),
+        err => err,
+    }
+}
+
+macro_rules! impl_from_row {
+    ($($ty:ident $index:tt),*) => {
+        /// Reads the leading columns of the row by index.
+        impl<$($ty: FromSql),*> FromRow for ($($ty,)*) {
+            fn from_row_at(row: &Row, offset: usize) -> Result<Self, rusqlite::Error> {
+                Ok(($(row.get(offset + $index)?,)*))
+            }
+        }
+    };
+}
+
+impl_from_row!(A 0);
+impl_from_row!(A 0, B 1);
+impl_from_row!(A 0, B 1, C 2);
+impl_from_row!(A 0, B 1, C 2, D 3);
+impl_from_row!(A 0, B 1, C 2, D 3, E 4);
+impl_from_row!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_from_row!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_from_row!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
@@ -0,0 +1,96 @@
+use crate::schema::column::Column;
+use crate::schema::aggregate::Aggregate;
+use crate::schema::Model;
+
+use rusqlite::types::FromSql;
+use rusqlite::Row;
+
+
+/// A single expression over the rows of `M` that can be selected, such as a column or an aggregate.
+pub trait Selectable<M> {
+    type Output: FromSql;
+
+    fn sql(&self) -> String;
+}
+
+impl<M: Model, T: FromSql> Selectable<M> for Column<M, T> {
+    type Output = T;
+
+    fn sql(&self) -> String {
+        self.qualified()
+    }
+}
+
+impl<M, T: FromSql> Selectable<M> for Aggregate<M, T> {
+    type Output = T;
+
+    fn sql(&self) -> String {
+        self.sql.clone()
+    }
+}
+
+/// A struct holding a subset of the columns of `M`, usually derived with `#[derive(Projection)]`.
+pub trait Projection<M>: Sized {
+    const COLUMNS: &'static [&'static str];
+
+    fn from_row(row: &Row, offset: usize) -> Result<Self, rusqlite::Error>;
+}
+
+/// The result columns of a query over `M`, decoded into `Output` for every row.
+pub trait Selection<M> {
+    type Output;
+
+    fn columns(&self) -> Vec<String>;
+
+    fn decode(row: &Row, offset: usize) -> Result<Self::Output, rusqlite::Error>;
+}
+
+impl<M: Model, T: FromSql> Selection<M> for Column<M, T> {
+    type Output = T;
+
+    fn columns(&self) -> Vec<String> {
+        vec![self.sql()]
+    }
+
+    fn decode(row: &Row, offset: usize) -> Result<T, rusqlite::Error> {
+        row.get(offset)
+    }
+}
+
+impl<M, T: FromSql> Selection<M> for Aggregate<M, T> {
+    type Output = T;
+
+    fn columns(&self) -> Vec<String> {
+        vec![self.sql()]
+    }
+
+    fn decode(row: &Row, offset: usize) -> Result<T, rusqlite::Error> {
+        row.get(offset)
+    }
+}
+
+macro_rules! impl_selection {
+    ($($ty:ident $index:tt),*) => {
+        impl<M, $($ty: Selectable<M>),*> Selection<M> for ($($ty,)*) {
+            type Output = ($($ty::Output,)*);
+
+            fn columns(&self) -> Vec<String> {
+                vec![$(self.$index.sql()),*]
+            }
+
+            fn decode(row: &Row, offset: usize) -> Result<Self::Output, rusqlite::Error> {
+                Ok(($(row.get(offset + $index)?,)*))
+            }
+        }
+    };
+}
+
+impl_selection!(A 0);
+impl_selection!(A 0, B 1);
+impl_selection!(A 0, B 1, C 2);
+impl_selection!(A 0, B 1, C 2, D 3);
+impl_selection!(A 0, B 1, C 2, D 3, E 4);
+impl_selection!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_selection!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_selection!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
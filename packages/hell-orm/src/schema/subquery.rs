@@ -0,0 +1,85 @@
+use crate::schema::query::Clauses;
+use crate::schema::Model;
+
+use rusqlite::ToSql;
+
+use std::marker::PhantomData;
+
+
+/// A part of a statement that binds parameters, rendered with the placeholders of the statement it ends up in.
+pub(crate) trait Fragment {
+    fn render(&self, index: &mut usize) -> String;
+
+    fn params(&self) -> Vec<&dyn ToSql>;
+
+    fn is_variable(&self) -> bool;
+}
+
+struct Select<Row> {
+    columns: String,
+    clauses: Clauses<Row>,
+}
+
+impl<Row: Model> Fragment for Select<Row> {
+    fn render(&self, index: &mut usize) -> String {
+        let filter = self.clauses.where_clause(index);
+
+        format!("{}{} {} FROM {}{}{}", self.clauses.with_clause(index), self.clauses.select_keyword(), self.columns, Row::NAME, filter, self.clauses.order_clause())
+    }
+
+    fn params(&self) -> Vec<&dyn ToSql> {
+        self.clauses.params().collect()
+    }
+
+    fn is_variable(&self) -> bool {
+        self.clauses.is_variable()
+    }
+}
+
+/// A query selecting values of type `T`, built with `subquery` and used inside the filters of another query.
+pub struct Subquery<T> {
+    pub(crate) select: Box<dyn Fragment>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Subquery<T> {
+    pub(crate) fn new<Row: Model + 'static>(columns: String, clauses: Clauses<Row>) -> Subquery<T> {
+        Subquery {
+            select: Box::new(Select { columns, clauses }),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Names the subquery as a common table expression, attached to a query with `with` and
+    /// read through `With::table`.
+    pub fn named(self, name: &'static str) -> With<T> {
+        With {
+            name,
+            select: self.select,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A plain, non-recursive `WITH name AS (subquery)`, built with `Subquery::named`.
+pub struct With<T> {
+    pub(crate) name: &'static str,
+    pub(crate) select: Box<dyn Fragment>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> With<T> {
+    /// The table the expression defines, to use with `Column::in_` in the query it is attached to.
+    pub fn table(&self) -> WithTable<T> {
+        WithTable {
+            name: self.name,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The table defined by a `With`, selecting values of type `T`.
+pub struct WithTable<T> {
+    pub(crate) name: &'static str,
+    _marker: PhantomData<fn() -> T>,
+}
@@ -0,0 +1,23 @@
+//! Lookup of the models of a schema by table name, used by `query_as!` to check SQL at compile time.
+
+/// Hashes a table name into the const parameter of `Table`.
+pub const fn table_hash(name: &str) -> u64 {
+    let bytes = name.as_bytes();
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        hash ^= bytes[index].to_ascii_lowercase() as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+        index += 1;
+    }
+
+    hash
+}
+
+/// Implemented by `#[derive(Schema)]` for the table of every listed model, keyed by `table_hash` of its name.
+#[diagnostic::on_unimplemented(message = "the table is not part of the schema `{Self}`", label = "unknown table")]
+pub trait Table<const HASH: u64> {
+    type Model;
+}
+
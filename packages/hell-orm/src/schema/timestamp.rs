@@ -0,0 +1,51 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+
+/// Types that can be filled in automatically by `#[created_at]` and `#[updated_at]` fields.
+pub trait Timestamp {
+    fn now() -> Self;
+}
+
+fn unix_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+macro_rules! impl_timestamp {
+    ($($ty:ty),*) => {
+        $(
+            impl Timestamp for $ty {
+                fn now() -> $ty {
+                    unix_seconds() as $ty
+                }
+            }
+        )*
+    };
+}
+
+impl_timestamp!(i64, u64, isize, usize);
+
+/// Formats the current time the same way as sqlite's `CURRENT_TIMESTAMP`.
+impl Timestamp for String {
+    fn now() -> String {
+        let seconds = unix_seconds();
+        let days = (seconds / 86400) as i64;
+        let time = seconds % 86400;
+
+        // civil date from days since 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + (month <= 2) as i64;
+
+        format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}", year, month, day, time / 3600, time % 3600 / 60, time % 60)
+    }
+}
+
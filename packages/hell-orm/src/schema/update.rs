@@ -0,0 +1,70 @@
+use crate::schema::query::{QueryBuilder, prepare};
+use crate::schema::column::Column;
+use crate::schema::condition::placeholder;
+use crate::schema::{Model, Versioned};
+use crate::error::Error;
+
+use rusqlite::ToSql;
+
+
+pub struct UpdateBuilder<'a, Row, S> {
+    pub query: QueryBuilder<'a, Row, S>,
+    versioned: bool,
+}
+
+impl<'a, Row: Model, S> UpdateBuilder<'a, Row, S> {
+    pub fn new(query: QueryBuilder<'a, Row, S>) -> UpdateBuilder<'a, Row, S> {
+        UpdateBuilder {
+            query,
+            versioned: false,
+        }
+    }
+
+    /// Only updates rows whose `#[version]` column still holds `version`.
+    pub fn expect_version(self, version: Row::Version) -> UpdateBuilder<'a, Row, S>
+    where
+        Row: Versioned
+    {
+        UpdateBuilder {
+            query: self.query.filter(Column::<Row, Row::Version>::new(Row::VERSION_COLUMN).eq(version)),
+            versioned: true,
+        }
+    }
+
+    pub fn finish(self, columns: &[&str], params: &[&dyn ToSql]) -> Result<usize, Error> {
+        // nothing to write, but a stale expected version must still be reported
+        if columns.is_empty() {
+            if self.versioned && !self.query.exists()? {
+                return Err(Error::StaleVersion);
+            }
+
+            return Ok(0);
+        }
+
+        let mut index = 0;
+
+        let mut assignments = columns.iter()
+            .map(|column| format!("{} = {}", column, placeholder(&mut index)))
+            .collect::<Vec<String>>();
+
+        if let Some(column) = Row::VERSION {
+            assignments.push(format!("{} = {} + 1", column, column));
+        }
+
+        let assignments = assignments.join(", ");
+
+        let sql = format!("{}UPDATE {} SET {}{}", self.query.with_clause(index), self.query.table_name, assignments, self.query.target_clause(index));
+
+        let mut stmt = prepare(self.query.connection, sql.as_str(), self.query.is_variable())?;
+
+        let changed = stmt.execute(rusqlite::params_from_iter(params.iter().copied().chain(self.query.params())))
+            .map_err(|err| Error::UpdateError(Box::new(err)))?;
+
+        if self.versioned && changed == 0 {
+            return Err(Error::StaleVersion);
+        }
+
+        Ok(changed)
+    }
+}
+
@@ -0,0 +1,104 @@
+use hell_orm::prelude::*;
+
+
+// only ever read through aggregates
+#[allow(dead_code)]
+#[derive(Model, Debug)]
+#[table_name = "posts"]
+struct Post {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    user: usize,
+    content: String,
+    score: i64,
+}
+
+#[derive(Model, Debug)]
+#[table_name = "stats"]
+struct Stat {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    count: i64,
+    max: i64,
+}
+
+#[derive(Schema)]
+#[models(Post, Stat)]
+struct Schema;
+
+/// Inserts `count` posts by `user`, scored `0..count`.
+fn posts(db: &mut Database<Schema>, user: usize, count: i64) {
+    for score in 0..count {
+        db.insert::<Post>().user(user).content(format!("post #{}", score)).score(score).finish().unwrap();
+    }
+}
+
+#[test]
+fn aggregates_over_matching_rows() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    posts(&mut db, 1, 4);
+
+    assert_eq!(db.query::<Post>().count().unwrap(), 4);
+    assert_eq!(db.query::<Post>().sum(Post::SCORE_COL).unwrap(), Some(6));
+    assert_eq!(db.query::<Post>().avg(Post::SCORE_COL).unwrap(), Some(1.5));
+    assert_eq!(db.query::<Post>().min(Post::CONTENT_COL).unwrap(), Some(String::from("post #0")));
+    assert_eq!(db.query::<Post>().filter(Post::SCORE_COL.lt(3)).max(Post::SCORE_COL).unwrap(), Some(2));
+}
+
+#[test]
+fn aggregates_over_no_rows_are_none() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    assert_eq!(db.query::<Post>().count().unwrap(), 0);
+    assert_eq!(db.query::<Post>().sum(Post::SCORE_COL).unwrap(), None);
+    assert_eq!(db.query::<Post>().avg(Post::SCORE_COL).unwrap(), None);
+}
+
+#[test]
+fn aggregates_honour_limit_and_offset() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    posts(&mut db, 1, 5);
+
+    assert_eq!(db.query::<Post>().limit(3).count().unwrap(), 3);
+    assert_eq!(db.query::<Post>().order_by(Post::SCORE_COL.desc()).limit(2).sum(Post::SCORE_COL).unwrap(), Some(7));
+    assert_eq!(db.query::<Post>().filter(Post::SCORE_COL.gt(0)).offset(3).max(Post::SCORE_COL).unwrap(), Some(4));
+}
+
+#[test]
+fn groups_filter_with_having() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    posts(&mut db, 1, 3);
+    posts(&mut db, 2, 1);
+
+    let groups = db.query::<Post>()
+        .group_by(Post::USER_COL)
+        .having(count().gt(1))
+        .select((Post::USER_COL, count(), Post::SCORE_COL.sum()))
+        .unwrap();
+
+    assert_eq!(groups, vec![(1, 3, Some(3))]);
+}
+
+#[test]
+fn fields_named_like_builder_methods_filter_with_a_prefix() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    for count in 0..3 {
+        db.insert::<Stat>().count(count).max(10).finish().unwrap();
+    }
+
+    db.query::<Stat>().where_count(Where::Equal(0)).update().count(5).finish().unwrap();
+
+    let stats = db.query::<Stat>().where_count(Where::GreaterThan(1)).where_max(Where::Equal(10)).all().unwrap();
+
+    assert_eq!(stats.iter().map(|stat| (stat.id, stat.count, stat.max)).collect::<Vec<_>>(), vec![(1, 5, 10), (3, 2, 10)]);
+    assert_eq!(db.query::<Stat>().where_count(Where::LessThan(5)).count().unwrap(), 2);
+    assert_eq!(db.query::<Stat>().sum(Stat::COUNT_COL).unwrap(), Some(8));
+}
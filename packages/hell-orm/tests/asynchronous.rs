@@ -0,0 +1,133 @@
+#![cfg(feature = "tokio")]
+
+use hell_orm::error::Error;
+use hell_orm::prelude::*;
+
+use std::future::Future;
+
+
+#[derive(Model, Debug)]
+#[table_name = "users"]
+struct User {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    name: String,
+}
+
+// only ever read through queries
+#[allow(dead_code)]
+#[derive(Model, Debug)]
+#[table_name = "comments"]
+struct Comment {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    body: String,
+}
+
+#[derive(Schema)]
+#[models(User, Comment)]
+struct Schema;
+
+fn database() -> Database<Schema> {
+    Database::open_in_memory().unwrap()
+}
+
+fn user(db: &mut Database<Schema>, name: &str) {
+    db.insert::<User>().name(String::from(name)).finish().unwrap();
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(future)
+}
+
+#[test]
+fn operations_run_on_the_worker() {
+    block_on(async {
+        let db = AsyncDatabase::new(database());
+
+        db.insert::<Comment, _, _>(|comment| comment.body(String::from("async")).finish()).await.unwrap();
+
+        let count = db.query::<Comment, _, _>(|query| query.count()).await.unwrap();
+        let deleted = db.query::<Comment, _, _>(|query| query.delete()).await.unwrap();
+
+        assert_eq!((count, deleted), (1, 1));
+    });
+}
+
+#[test]
+fn panics_do_not_stop_the_worker() {
+    block_on(async {
+        let db = AsyncDatabase::new(database());
+
+        let result = db.run(|db| {
+            db.execute_raw("BEGIN", [])?;
+            db.insert::<Comment>().body(String::from("lost")).finish()?;
+
+            if true {
+                panic!("boom");
+            }
+
+            Ok(())
+        })
+        .await;
+
+        assert!(matches!(result, Err(Error::ThreadError(message)) if message.to_string() == "boom"));
+
+        // the panicked transaction was rolled back, so a new one can start
+        let count = db.transaction(|db| db.query::<Comment>().count()).await.unwrap();
+
+        assert_eq!(count, 0);
+    });
+}
+
+#[test]
+fn transactions_commit_or_roll_back() {
+    block_on(async {
+        let db = AsyncDatabase::new(database());
+
+        db.transaction(|db| {
+            user(db, "ada");
+
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        let failed = db.transaction(|db| {
+            user(db, "grace");
+
+            Err::<(), _>(Error::StaleVersion)
+        })
+        .await;
+
+        assert!(matches!(failed, Err(Error::StaleVersion)));
+        let names = db.query::<User, _, _>(|query| query.all()).await.unwrap().into_iter().map(|user| user.name).collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["ada"]);
+    });
+}
+
+#[test]
+fn updates_and_deletes_change_the_filtered_rows() {
+    block_on(async {
+        let mut db = database();
+
+        for name in ["ada", "grace", "linus"] {
+            user(&mut db, name);
+        }
+
+        let db = AsyncDatabase::new(db);
+
+        let updated = db.update::<User, _>(|query| query.filter(User::NAME_COL.eq("ada")).update().name(String::from("lovelace")).finish()).await.unwrap();
+        let deleted = db.delete::<User, _>(|query| query.filter(User::NAME_COL.eq("grace")).delete()).await.unwrap();
+
+        assert_eq!((updated, deleted), (1, 1));
+        let names = db.query::<User, _, _>(|query| query.all()).await.unwrap().into_iter().map(|user| user.name).collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["lovelace", "linus"]);
+    });
+}
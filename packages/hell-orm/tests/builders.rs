@@ -0,0 +1,33 @@
+use hell_orm::prelude::*;
+
+
+#[derive(Model, Debug)]
+#[table_name = "posts"]
+struct Post {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    content: String,
+    score: i64,
+}
+
+#[derive(Schema)]
+#[models(Post)]
+struct Schema;
+
+#[test]
+fn field_filters_narrow_queries_and_updates() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    for score in 0..4 {
+        db.insert::<Post>().content(format!("post #{}", score)).score(score).finish().unwrap();
+    }
+
+    let high = db.query::<Post>().score(Where::GreaterOrEqual(2)).all().unwrap();
+
+    assert!(high.len() == 2 && high.iter().all(|post| post.score >= 2));
+    assert_eq!(db.query::<Post>().score(Where::LessThan(1)).update().content(String::from("low")).finish().unwrap(), 1);
+    assert_eq!(db.query::<Post>().score(Where::Equal(0)).get().unwrap().unwrap().content, "low");
+    assert!(db.query::<Post>().content(Where::NotEqual(String::from("low"))).score(Where::Equal(0)).get().unwrap().is_none());
+}
@@ -0,0 +1,63 @@
+use hell_orm::prelude::*;
+
+
+#[derive(Model, Debug)]
+#[table_name = "posts"]
+struct Post {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    content: String,
+    score: i64,
+}
+
+#[derive(Model, Debug)]
+#[table_name = "tokens"]
+struct Token {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    r#type: String,
+}
+
+#[derive(Schema)]
+#[models(Post, Token)]
+struct Schema;
+
+#[test]
+fn column_constants_filter_by_value() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    for score in 0..4 {
+        db.insert::<Post>().content(format!("post #{}", score)).score(score).finish().unwrap();
+    }
+
+    let posts = db.query::<Post>().filter(Post::SCORE_COL.gt(1)).all().unwrap();
+
+    assert_eq!(posts.iter().map(|post| post.score).collect::<Vec<i64>>(), vec![2, 3]);
+
+    let post = db.query::<Post>().filter(Post::CONTENT_COL.eq("post #0")).get().unwrap().unwrap();
+
+    assert_eq!((post.id, post.content.as_str()), (1, "post #0"));
+}
+
+#[test]
+fn column_constants_name_their_column() {
+    assert_eq!(Post::SCORE_COL.name, "score");
+    assert_eq!(Post::SCORE_COL.qualified(), "posts.score");
+    assert_eq!(Post::SCORE_COL.table(), "posts");
+}
+
+#[test]
+fn raw_identifiers_drop_their_prefix() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<Token>().r#type(String::from("keyword")).finish().unwrap();
+
+    let token = db.query::<Token>().filter(Token::TYPE_COL.eq("keyword")).get().unwrap().unwrap();
+
+    assert_eq!((token.id, token.r#type.as_str()), (1, "keyword"));
+    assert_eq!(Token::TYPE_COL.name, "type");
+}
@@ -0,0 +1,19 @@
+//! Fixtures shared by the integration tests, every test binary only uses some of them.
+#![allow(dead_code)]
+
+pub mod posts;
+pub mod users;
+
+use std::path::PathBuf;
+
+
+/// A fresh database file path in the temporary directory, unique to `name` and the test process.
+pub fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("hell-orm-{}-{}.db", name, std::process::id()));
+
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+    }
+
+    path
+}
@@ -0,0 +1,41 @@
+//! Scored posts on their own, plus comments for nullable columns.
+
+use hell_orm::prelude::*;
+
+
+#[derive(Model, Debug)]
+#[table_name = "posts"]
+pub struct Post {
+    #[primary_key]
+    #[auto_increment]
+    pub id: usize,
+
+    pub content: String,
+    pub score: i64,
+}
+
+#[derive(Model, Debug)]
+#[table_name = "comments"]
+pub struct Comment {
+    #[primary_key]
+    #[auto_increment]
+    pub id: usize,
+
+    pub parent: Option<usize>,
+    pub body: String,
+}
+
+#[derive(Schema)]
+#[models(Post, Comment)]
+pub struct Schema;
+
+/// A database with `count` posts scored `0..count`.
+pub fn database(count: i64) -> Database<Schema> {
+    let mut db = Database::open_in_memory().unwrap();
+
+    for score in 0..count {
+        db.insert::<Post>().content(format!("post #{}", score)).score(score).finish().unwrap();
+    }
+
+    db
+}
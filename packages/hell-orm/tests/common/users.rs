@@ -0,0 +1,49 @@
+//! Users owning soft deleted, scored posts.
+
+use hell_orm::prelude::*;
+
+
+#[derive(Model, Debug)]
+#[table_name = "users"]
+pub struct User {
+    #[primary_key]
+    #[auto_increment]
+    pub id: usize,
+
+    pub name: String,
+}
+
+#[derive(Model, Debug)]
+#[table_name = "posts"]
+#[soft_delete]
+#[belongs_to(User, key = "user")]
+pub struct Post {
+    #[primary_key]
+    #[auto_increment]
+    pub id: usize,
+
+    pub user: usize,
+    pub content: String,
+    pub score: i64,
+}
+
+#[derive(Schema)]
+#[models(User, Post)]
+pub struct Schema;
+
+pub fn database() -> Database<Schema> {
+    Database::open_in_memory().unwrap()
+}
+
+/// Inserts a user with `count` posts scored `0..count`, returning the user's id.
+pub fn user_with_posts(db: &mut Database<Schema>, name: &str, count: i64) -> usize {
+    db.insert::<User>().name(String::from(name)).finish().unwrap();
+
+    let user = db.query::<User>().filter(User::NAME_COL.eq(name)).get().unwrap().unwrap();
+
+    for score in 0..count {
+        db.insert::<Post>().user(user.id).content(format!("{} #{}", name, score)).score(score).finish().unwrap();
+    }
+
+    user.id
+}
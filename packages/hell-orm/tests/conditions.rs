@@ -0,0 +1,76 @@
+mod common;
+
+use common::posts::{database, Comment, Post, Schema};
+
+use hell_orm::prelude::*;
+
+
+/// The scores of the matching posts, sorted.
+fn scores(db: &mut Database<Schema>, condition: Condition<Post>) -> Vec<i64> {
+    let mut scores = db.query::<Post>().filter(condition).all().unwrap().into_iter().map(|post| post.score).collect::<Vec<i64>>();
+
+    scores.sort();
+    scores
+}
+
+#[test]
+fn conditions_combine_with_and_or_not() {
+    let mut db = database(6);
+
+    let score = Post::SCORE_COL;
+
+    assert_eq!(scores(&mut db, score.lt(2).or(score.gt(4))), vec![0, 1, 5]);
+    assert_eq!(scores(&mut db, score.ge(1).and(score.le(4)).and(score.eq(2).not())), vec![1, 3, 4]);
+}
+
+#[test]
+fn operators_match_rows() {
+    let mut db = database(6);
+
+    assert_eq!(scores(&mut db, Post::SCORE_COL.in_([1, 4, 9])), vec![1, 4]);
+    assert_eq!(scores(&mut db, Post::SCORE_COL.between(2, 3)), vec![2, 3]);
+    assert_eq!(scores(&mut db, Post::CONTENT_COL.like("POST #5")), vec![5]);
+    assert_eq!(scores(&mut db, Post::CONTENT_COL.glob("post #[03]")), vec![0, 3]);
+    assert_eq!(scores(&mut db, Post::SCORE_COL.in_(Vec::<i64>::new())), Vec::<i64>::new());
+}
+
+#[test]
+fn null_checks_match_optional_columns() {
+    let mut db = database(0);
+
+    db.insert::<Comment>().body(String::from("root")).finish().unwrap();
+    db.insert::<Comment>().parent(Some(1)).body(String::from("reply")).finish().unwrap();
+
+    let roots = db.query::<Comment>().filter(Comment::PARENT_COL.is_null()).all().unwrap();
+    let replies = db.query::<Comment>().filter(Comment::PARENT_COL.is_not_null()).all().unwrap();
+
+    assert_eq!((roots[0].id, roots[0].body.as_str()), (1, "root"));
+    assert_eq!((replies[0].parent, replies[0].body.as_str()), (Some(1), "reply"));
+}
+
+#[test]
+fn placeholders_are_numbered_after_bound_parameters() {
+    let condition = Post::SCORE_COL.between(1, 3).or(Post::CONTENT_COL.eq("x").not());
+
+    let mut index = 2;
+
+    assert_eq!(condition.render(&mut index), "(posts.score BETWEEN ?3 AND ?4 OR NOT (posts.content = ?5))");
+    assert_eq!(index, 5);
+    assert_eq!(condition.params().len(), 3);
+}
+
+#[test]
+fn placeholders_follow_updated_values() {
+    let mut db = database(4);
+
+    let updated = db.query::<Post>()
+        .filter(Post::SCORE_COL.in_([1, 2]).or(Post::CONTENT_COL.eq("post #3")))
+        .update()
+        .content(String::from("changed"))
+        .finish()
+        .unwrap();
+
+    assert_eq!(updated, 3);
+    assert_eq!(scores(&mut db, Post::CONTENT_COL.eq("changed")), vec![1, 2, 3]);
+    assert_eq!(db.query::<Post>().filter(Post::SCORE_COL.eq(0)).get().unwrap().unwrap().content, "post #0");
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::temp_path;
+
+use hell_orm::options::{JournalMode, OpenOptions, Synchronous, TempStore};
+use hell_orm::prelude::*;
+
+use std::time::Duration;
+
+
+#[derive(Model, Debug)]
+#[table_name = "users"]
+struct User {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+}
+
+#[derive(Schema)]
+#[models(User)]
+struct Schema;
+
+fn pragma(db: &mut Database<Schema>, name: &str) -> String {
+    db.with_connection(|connection| connection.query_row(&format!("PRAGMA {}", name), [], |row| row.get::<_, rusqlite::types::Value>(0)))
+        .map(|value| match value {
+            rusqlite::types::Value::Integer(integer) => integer.to_string(),
+            rusqlite::types::Value::Text(text) => text,
+            value => format!("{:?}", value),
+        })
+        .unwrap()
+}
+
+#[test]
+fn config_applies_pragmas() {
+    let config = DatabaseConfig::new()
+        .wal()
+        .synchronous(Synchronous::Normal)
+        .busy_timeout(Duration::from_millis(1500))
+        .foreign_keys(true)
+        .cache_size(-4096)
+        .temp_store(TempStore::Memory);
+
+    let mut db = Database::<Schema>::open(temp_path("config"), config).unwrap();
+
+    assert_eq!(pragma(&mut db, "journal_mode"), "wal");
+    assert_eq!(pragma(&mut db, "synchronous"), "1");
+    assert_eq!(pragma(&mut db, "busy_timeout"), "1500");
+    assert_eq!(pragma(&mut db, "foreign_keys"), "1");
+    assert_eq!(pragma(&mut db, "cache_size"), "-4096");
+    assert_eq!(pragma(&mut db, "temp_store"), "2");
+}
+
+#[test]
+fn in_memory_databases_apply_the_config() {
+    let config = DatabaseConfig::new().journal_mode(JournalMode::Off).foreign_keys(true);
+
+    let mut db = Database::<Schema>::open_in_memory_with(config).unwrap();
+
+    assert_eq!(pragma(&mut db, "journal_mode"), "off");
+    assert_eq!(pragma(&mut db, "foreign_keys"), "1");
+}
+
+#[test]
+fn unset_settings_keep_defaults() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    assert_eq!(pragma(&mut db, "cache_size"), "-2000");
+}
+
+#[test]
+fn mmap_size_is_clamped() {
+    let path = temp_path("mmap");
+
+    // SQLite caps the size at its compile time maximum, but a wrapped size would turn negative and be ignored
+    let mut db = Database::<Schema>::open(&path, DatabaseConfig::new().mmap_size(4096)).unwrap();
+    let small = pragma(&mut db, "mmap_size");
+
+    let mut db = Database::<Schema>::open(&path, DatabaseConfig::new().mmap_size(u64::MAX)).unwrap();
+
+    assert_eq!(small, "4096");
+    assert_ne!(pragma(&mut db, "mmap_size"), "0");
+}
+
+#[test]
+fn open_with_keeps_the_options_literal() {
+    let options = OpenOptions {
+        flags: OpenOptions::default().flags,
+        vfs: None,
+        pragmas: Vec::new(),
+    };
+
+    let db = Database::<Schema>::open_with(temp_path("literal"), options);
+
+    assert!(db.is_ok());
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::users::{database, user_with_posts, User};
+
+use hell_orm::prelude::*;
+
+
+#[derive(FromRow)]
+struct Named {
+    name: String,
+    r#type: String,
+}
+
+#[derive(FromRow)]
+struct Activity {
+    #[row(flatten)]
+    user: User,
+
+    #[row(index = 2)]
+    posts: i64,
+
+    #[row(index = 3)]
+    last: Option<String>,
+}
+
+#[test]
+fn reads_fields_by_column_name() {
+    let mut db = database();
+
+    let rows = db.query_raw::<Named>("SELECT type, name FROM sqlite_master WHERE name = ?1", ["users"]).unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!((rows[0].name.as_str(), rows[0].r#type.as_str()), ("users", "table"));
+}
+
+#[test]
+fn reads_flattened_models_and_indexed_fields() {
+    let mut db = database();
+
+    let ada = user_with_posts(&mut db, "ada", 2);
+    let grace = user_with_posts(&mut db, "grace", 0);
+
+    let rows = db.query_raw::<Activity>("SELECT users.id, users.name, COUNT(posts.id), MAX(posts.content) FROM users LEFT JOIN posts ON posts.user = users.id GROUP BY users.id ORDER BY users.id", []).unwrap();
+
+    let rows = rows.into_iter().map(|row| (row.user.id, row.user.name, row.posts, row.last)).collect::<Vec<_>>();
+
+    assert_eq!(rows, vec![(ada, String::from("ada"), 2, Some(String::from("ada #1"))), (grace, String::from("grace"), 0, None)]);
+}
+
+#[test]
+fn errors_name_the_field() {
+    let mut db = database();
+
+    let err = db.query_raw::<Named>("SELECT name FROM sqlite_master", []).err().unwrap();
+
+    assert!(err.to_string().contains("type"), "{}", err);
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use common::users::{user_with_posts, Post, Schema, User};
+
+use hell_orm::prelude::*;
+
+
+#[test]
+fn include_groups_children_by_parent() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    let ada = user_with_posts(&mut db, "ada", 3);
+    let grace = user_with_posts(&mut db, "grace", 0);
+
+    let users = db.query::<User>().include::<Post>().all().unwrap();
+
+    assert_eq!(users.len(), 2);
+
+    for (user, posts) in users {
+        if user.name == "ada" {
+            assert_eq!((user.id, posts.len()), (ada, 3));
+        } else {
+            assert_eq!(user.id, grace);
+            assert!(posts.is_empty());
+        }
+    }
+}
+
+#[test]
+fn include_loads_children_of_many_parents() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    for index in 0..1200 {
+        user_with_posts(&mut db, &format!("user {}", index), 1);
+    }
+
+    let users = db.query::<User>().include::<Post>().all().unwrap();
+
+    assert_eq!(users.len(), 1200);
+    assert!(users.iter().all(|(user, posts)| posts.len() == 1 && posts[0].user == user.id));
+}
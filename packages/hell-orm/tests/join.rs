@@ -0,0 +1,88 @@
+use hell_orm::prelude::*;
+
+
+#[derive(Model, Debug)]
+#[table_name = "users"]
+struct User {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    name: String,
+}
+
+#[derive(Model, Debug)]
+#[table_name = "posts"]
+struct Post {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    user: usize,
+}
+
+#[derive(Model, Debug)]
+#[table_name = "comments"]
+struct Comment {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    parent: Option<usize>,
+    body: String,
+}
+
+#[derive(Schema)]
+#[models(User, Post, Comment)]
+struct Schema;
+
+#[test]
+fn inner_join_pairs_rows() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<User>().name(String::from("ada")).finish().unwrap();
+
+    let ada = db.query::<User>().get().unwrap().unwrap();
+
+    for user in [ada.id, ada.id, ada.id + 1] {
+        db.insert::<Post>().user(user).finish().unwrap();
+    }
+
+    let rows = db.query::<Post>().join::<User>(on!(Post::user == User::id)).all().unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|(post, user)| post.user == user.id && user.name == "ada"));
+}
+
+#[test]
+fn left_join_keeps_rows_without_a_match() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<Comment>().body(String::from("root")).finish().unwrap();
+
+    let rows = db.query::<Comment>().left_join::<Comment>(on!(Comment::parent == Comment::id)).all().unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert!(rows[0].1.is_none());
+}
+
+#[test]
+fn self_join_tells_both_sides_apart() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<Comment>().body(String::from("root")).finish().unwrap();
+
+    let root = db.query::<Comment>().get().unwrap().unwrap();
+
+    db.insert::<Comment>().parent(Some(root.id)).body(String::from("reply")).finish().unwrap();
+
+    let rows = db.query::<Comment>()
+        .body(Where::Equal(String::from("reply")))
+        .join::<Comment>(on!(Comment::parent == Comment::id))
+        .all()
+        .unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!((rows[0].0.body.as_str(), rows[0].0.parent), ("reply", Some(root.id)));
+    assert_eq!(rows[0].1.body, "root");
+}
@@ -0,0 +1,99 @@
+use hell_orm::prelude::*;
+
+
+#[derive(Model, Debug)]
+#[table_name = "posts"]
+#[many_to_many(Tag, through = PostTag)]
+#[many_to_many(Type, through = PostType)]
+struct Post {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    content: String,
+}
+
+#[derive(Model, Debug)]
+#[table_name = "tags"]
+struct Tag {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    label: String,
+}
+
+#[derive(Model, Debug)]
+#[table_name = "post_tags"]
+#[belongs_to(Post, key = "post")]
+#[belongs_to(Tag, key = "tag")]
+struct PostTag {
+    post: usize,
+    tag: usize,
+}
+
+/// Named after a keyword, which the generated methods must still compile with.
+#[derive(Model, Debug)]
+#[table_name = "types"]
+struct Type {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    name: String,
+}
+
+#[derive(Model, Debug)]
+#[table_name = "post_types"]
+#[belongs_to(Post, key = "post")]
+#[belongs_to(Type, key = "kind")]
+struct PostType {
+    post: usize,
+    kind: usize,
+}
+
+#[derive(Schema)]
+#[models(Post, Tag, PostTag, Type, PostType)]
+struct Schema;
+
+#[test]
+fn many_to_many_links_and_unlinks() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<Post>().content(String::from("hello")).finish().unwrap();
+    db.insert::<Tag>().label(String::from("rust")).finish().unwrap();
+    db.insert::<Tag>().label(String::from("sql")).finish().unwrap();
+
+    let post = db.query::<Post>().get().unwrap().unwrap();
+    let tags = db.query::<Tag>().all().unwrap();
+
+    for tag in tags.iter() {
+        post.add_tag(&mut db, tag).unwrap();
+    }
+
+    assert_eq!(post.tags(&mut db).unwrap().len(), 2);
+    assert_eq!(post.remove_tag(&mut db, &tags[0]).unwrap(), 1);
+
+    let remaining = post.tags(&mut db).unwrap();
+
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].label, tags[1].label);
+}
+
+#[test]
+fn keyword_models_link_through_a_fixed_argument() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<Post>().content(String::from("hello")).finish().unwrap();
+    db.insert::<Type>().name(String::from("article")).finish().unwrap();
+
+    let post = db.query::<Post>().get().unwrap().unwrap();
+    let kind = db.query::<Type>().get().unwrap().unwrap();
+
+    post.add_type(&mut db, &kind).unwrap();
+
+    assert_eq!(post.types(&mut db).unwrap()[0].name, "article");
+    assert_eq!(post.post_types(&mut db).unwrap()[0].kind, kind.id);
+    assert_eq!(post.remove_type(&mut db, &kind).unwrap(), 1);
+    assert_eq!(post.content, "hello");
+}
@@ -0,0 +1,60 @@
+use hell_orm::prelude::*;
+
+
+#[derive(Model, Debug)]
+#[table_name = "comments"]
+struct Comment {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    parent: Option<usize>,
+    body: String,
+}
+
+#[derive(Schema)]
+#[models(Comment)]
+struct Schema;
+
+fn database() -> Database<Schema> {
+    Database::open_in_memory().unwrap()
+}
+
+#[test]
+fn option_fields_store_null() {
+    let mut db = database();
+
+    db.insert::<Comment>().parent(None).body(String::from("root")).finish().unwrap();
+
+    let comment = db.query::<Comment>().get().unwrap().unwrap();
+
+    assert_eq!((comment.parent, comment.body.as_str()), (None, "root"));
+    assert_eq!(db.query::<Comment>().filter(Comment::PARENT_COL.is_null()).all().unwrap().len(), 1);
+}
+
+#[test]
+fn unset_option_fields_store_null() {
+    let mut db = database();
+
+    db.insert::<Comment>().body(String::from("root")).finish().unwrap();
+
+    assert_eq!(db.query::<Comment>().get().unwrap().unwrap().parent, None);
+}
+
+#[test]
+fn option_setters_store_their_value() {
+    let mut db = database();
+
+    db.insert::<Comment>().parent(Some(7)).body(String::from("reply")).finish().unwrap();
+
+    assert_eq!(db.query::<Comment>().get().unwrap().unwrap().parent, Some(7));
+}
+
+#[test]
+fn required_fields_reject_null() {
+    let mut db = database();
+
+    // the typed builder won't finish without a body, so go through the untyped insert it wraps
+    assert!(db.insert::<Comment>().builder.finish(&["parent"], [7]).is_err());
+    assert!(db.query::<Comment>().all().unwrap().is_empty());
+}
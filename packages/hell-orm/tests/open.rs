@@ -0,0 +1,87 @@
+mod common;
+
+use common::temp_path;
+
+use hell_orm::options::OpenOptions;
+use hell_orm::error::Error;
+use hell_orm::prelude::*;
+
+
+#[derive(Model, Debug)]
+#[table_name = "users"]
+struct User {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    name: String,
+}
+
+#[derive(Schema)]
+#[models(User)]
+struct Schema;
+
+#[derive(Model)]
+#[table_name = "missing"]
+struct Missing {
+    #[primary_key]
+    id: usize,
+}
+
+#[derive(Schema)]
+#[models(Missing)]
+struct MissingSchema;
+
+fn user(db: &mut Database<Schema>, name: &str) {
+    db.insert::<User>().name(String::from(name)).finish().unwrap();
+}
+
+#[test]
+fn in_memory_databases_are_private() {
+    let mut first = Database::<Schema>::open_in_memory().unwrap();
+    let mut second = Database::<Schema>::open_in_memory().unwrap();
+
+    user(&mut first, "ada");
+
+    assert_eq!(first.query::<User>().count().unwrap(), 1);
+    assert_eq!(second.query::<User>().count().unwrap(), 0);
+}
+
+#[test]
+fn read_only_databases_reject_writes() {
+    let path = temp_path("read-only");
+
+    let mut db = Database::<Schema>::open(&path, DatabaseConfig::new()).unwrap();
+
+    user(&mut db, "ada");
+
+    let mut reader = Database::<Schema>::open_read_only(&path).unwrap();
+
+    assert_eq!(reader.query::<User>().all().unwrap().into_iter().map(|user| user.name).collect::<Vec<_>>(), vec!["ada"]);
+    assert!(reader.insert::<User>().name(String::from("grace")).finish().is_err());
+}
+
+#[test]
+fn read_only_databases_verify_the_schema() {
+    let path = temp_path("verify");
+
+    Database::<Schema>::open(&path, DatabaseConfig::new()).unwrap();
+
+    assert!(matches!(Database::<MissingSchema>::open_read_only(&path), Err(Error::SchemaError(_))));
+}
+
+#[test]
+fn open_with_applies_pragmas() {
+    let path = temp_path("pragmas");
+
+    let options = OpenOptions {
+        pragmas: vec![(String::from("user_version"), String::from("7"))],
+        ..OpenOptions::default()
+    };
+
+    let mut db = Database::<Schema>::open_with(&path, options).unwrap();
+
+    let version = db.with_connection(|connection| connection.query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))).unwrap();
+
+    assert_eq!(version, 7);
+}
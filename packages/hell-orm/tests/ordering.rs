@@ -0,0 +1,73 @@
+mod common;
+
+use common::posts::{database, Post};
+
+
+fn scores(posts: Vec<Post>) -> Vec<i64> {
+    posts.into_iter().map(|post| post.score).collect()
+}
+
+#[test]
+fn order_limit_and_offset_page_through_rows() {
+    let mut db = database(6);
+
+    let posts = db.query::<Post>().order_by(Post::SCORE_COL.desc()).limit(2).offset(1).all().unwrap();
+
+    assert_eq!(scores(posts), vec![4, 3]);
+
+    let posts = db.query::<Post>().order_by(Post::SCORE_COL.asc()).offset(4).all().unwrap();
+
+    assert_eq!(scores(posts), vec![4, 5]);
+}
+
+#[test]
+fn later_sort_keys_break_ties() {
+    let mut db = database(0);
+
+    for (content, score) in [("b", 1), ("a", 1), ("c", 0)] {
+        db.insert::<Post>().content(String::from(content)).score(score).finish().unwrap();
+    }
+
+    let posts = db.query::<Post>().order_by(Post::SCORE_COL.desc()).order_by(Post::CONTENT_COL.asc()).all().unwrap();
+
+    assert_eq!(posts.iter().map(|post| post.content.as_str()).collect::<Vec<&str>>(), vec!["a", "b", "c"]);
+}
+
+#[test]
+fn delete_honours_order_and_limit() {
+    let mut db = database(5);
+
+    let deleted = db.query::<Post>().order_by(Post::SCORE_COL.desc()).limit(2).delete().unwrap();
+
+    assert_eq!(deleted, 2);
+    assert_eq!(scores(db.query::<Post>().order_by(Post::SCORE_COL.asc()).all().unwrap()), vec![0, 1, 2]);
+}
+
+#[test]
+fn delete_honours_offset() {
+    let mut db = database(3);
+
+    assert_eq!(db.query::<Post>().order_by(Post::SCORE_COL.desc()).limit(1).offset(1).delete().unwrap(), 1);
+    assert_eq!(scores(db.query::<Post>().order_by(Post::SCORE_COL.asc()).all().unwrap()), vec![0, 2]);
+}
+
+#[test]
+fn update_honours_order_limit_and_offset() {
+    let mut db = database(5);
+
+    let updated = db.query::<Post>()
+        .filter(Post::SCORE_COL.ge(1))
+        .order_by(Post::SCORE_COL.asc())
+        .limit(2)
+        .offset(1)
+        .update()
+        .content(String::from("changed"))
+        .finish()
+        .unwrap();
+
+    assert_eq!(updated, 2);
+
+    let posts = db.query::<Post>().filter(Post::CONTENT_COL.eq("changed")).order_by(Post::SCORE_COL.asc()).all().unwrap();
+
+    assert_eq!(scores(posts), vec![2, 3]);
+}
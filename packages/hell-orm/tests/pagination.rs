@@ -0,0 +1,113 @@
+mod common;
+
+use common::posts::{database, Comment, Post};
+
+use hell_orm::error::Error;
+use hell_orm::schema::page::Cursor;
+
+
+#[test]
+fn pages_walk_every_row_once() {
+    let mut db = database(5);
+
+    let mut cursor: Option<Cursor> = None;
+    let mut pages = Vec::new();
+
+    loop {
+        let page = db.query::<Post>().order_by(Post::SCORE_COL.desc()).paginate_after(cursor.as_ref(), 2).unwrap();
+
+        pages.push(page.items.iter().map(|post| post.score).collect::<Vec<i64>>());
+
+        match page.next {
+            Some(next) => cursor = Some(next.to_string().parse().unwrap()),
+            None => break,
+        }
+    }
+
+    assert_eq!(pages, vec![vec![4, 3], vec![2, 1], vec![0]]);
+}
+
+#[test]
+fn huge_page_size_does_not_overflow() {
+    let mut db = database(3);
+
+    let page = db.query::<Post>().paginate_after(None, usize::MAX).unwrap();
+
+    assert_eq!(page.items.len(), 3);
+    assert!(page.next.is_none());
+}
+
+#[test]
+fn cursors_round_trip_text_keys() {
+    let mut db = database(3);
+
+    let page = db.query::<Post>().order_by(Post::CONTENT_COL.asc()).paginate_after(None, 1).unwrap();
+    let cursor = page.next.unwrap();
+
+    assert_eq!(cursor.to_string().parse::<Cursor>().unwrap(), cursor);
+
+    let page = db.query::<Post>().order_by(Post::CONTENT_COL.asc()).paginate_after(Some(&cursor), 5).unwrap();
+
+    assert_eq!(page.items.iter().map(|post| post.content.as_str()).collect::<Vec<&str>>(), vec!["post #1", "post #2"]);
+}
+
+#[test]
+fn cursors_must_match_the_sort_keys() {
+    let mut db = database(3);
+
+    let by_score = db.query::<Post>().order_by(Post::SCORE_COL.asc()).paginate_after(None, 1).unwrap().next.unwrap();
+
+    let wrong_type = db.query::<Post>().order_by(Post::CONTENT_COL.asc()).paginate_after(Some(&by_score), 1);
+    let wrong_length = db.query::<Post>().paginate_after(Some(&by_score), 1);
+    // a version 1 cursor holding a single NULL
+    let null = db.query::<Post>().paginate_after(Some(&"0100".parse().unwrap()), 1);
+
+    assert!(matches!(wrong_type, Err(Error::InvalidCursor)));
+    assert!(matches!(wrong_length, Err(Error::InvalidCursor)));
+    assert!(matches!(null, Err(Error::InvalidCursor)));
+}
+
+#[test]
+fn cursors_are_opaque() {
+    let mut db = database(3);
+
+    let cursor = db.query::<Post>().order_by(Post::CONTENT_COL.asc()).paginate_after(None, 1).unwrap().next.unwrap().to_string();
+
+    assert!(cursor.chars().all(|char| char.is_ascii_hexdigit()));
+    assert!(!cursor.contains("post"));
+}
+
+#[test]
+fn malformed_cursors_are_rejected() {
+    for cursor in ["", "x1", "010", "0101", "0200", "0105"] {
+        assert!(matches!(cursor.parse::<Cursor>(), Err(Error::InvalidCursor)), "{:?}", cursor);
+    }
+}
+
+#[test]
+fn sort_keys_must_share_a_direction() {
+    let mut db = database(0);
+
+    let page = db.query::<Post>().order_by(Post::SCORE_COL.asc()).order_by(Post::CONTENT_COL.desc()).paginate_after(None, 1);
+
+    assert!(matches!(page, Err(Error::InvalidPageOrder)));
+}
+
+#[test]
+fn null_sort_keys_are_rejected() {
+    let mut db = database(0);
+
+    db.insert::<Comment>().body(String::from("a")).finish().unwrap();
+    db.insert::<Comment>().parent(Some(1)).body(String::from("b")).finish().unwrap();
+
+    let page = db.query::<Comment>().order_by(Comment::PARENT_COL.asc()).paginate_after(None, 1);
+
+    assert!(matches!(page, Err(Error::InvalidPageOrder)));
+
+    // a last page has no cursor to build, so its NULL keys are fine
+    let page = db.query::<Comment>().order_by(Comment::PARENT_COL.asc()).paginate_after(None, 2).unwrap();
+
+    let comments = page.items.into_iter().map(|comment| (comment.parent, comment.body)).collect::<Vec<_>>();
+
+    assert_eq!(comments, vec![(None, String::from("a")), (Some(1), String::from("b"))]);
+}
@@ -0,0 +1,148 @@
+mod common;
+
+use common::temp_path;
+
+use hell_orm::error::Error;
+use hell_orm::prelude::*;
+
+use std::sync::Barrier;
+use std::time::Duration;
+
+
+#[derive(Model, Debug)]
+#[table_name = "users"]
+struct User {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    name: String,
+}
+
+#[derive(Schema)]
+#[models(User)]
+struct Schema;
+
+fn pool(name: &str, readers: usize) -> Pool<Schema> {
+    Pool::open(temp_path(name), readers, DatabaseConfig::new().wal().busy_timeout(Duration::from_secs(5))).unwrap()
+}
+
+fn users(db: &mut Database<Schema>, count: usize) {
+    for index in 0..count {
+        db.insert::<User>().name(format!("user #{}", index)).finish().unwrap();
+    }
+}
+
+#[test]
+fn readers_see_committed_writes() {
+    let pool = pool("writes", 2);
+
+    users(&mut pool.writer().unwrap(), 3);
+
+    let counts = std::thread::scope(|scope| {
+        let workers = (0..4)
+            .map(|_| scope.spawn(|| pool.reader().unwrap().query::<User>().count().unwrap()))
+            .collect::<Vec<_>>();
+
+        workers.into_iter().map(|worker| worker.join().unwrap()).collect::<Vec<i64>>()
+    });
+
+    assert_eq!(counts, vec![3; 4]);
+}
+
+#[test]
+fn readers_cannot_write() {
+    let pool = pool("read-only", 1);
+
+    users(&mut pool.writer().unwrap(), 1);
+
+    let mut reader = pool.reader().unwrap();
+
+    assert!(reader.query::<User>().delete().is_err());
+    assert_eq!(reader.query::<User>().get().unwrap().unwrap().name, "user #0");
+}
+
+#[test]
+fn try_and_timeout_variants_give_up() {
+    let pool = pool("timeout", 1);
+    let held = Barrier::new(2);
+    let checked = Barrier::new(2);
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            let _writer = pool.writer().unwrap();
+            let _reader = pool.reader().unwrap();
+
+            held.wait();
+            checked.wait();
+        });
+
+        held.wait();
+
+        assert!(pool.try_writer().is_none());
+        assert!(pool.try_reader().is_none());
+        assert!(matches!(pool.writer_timeout(Duration::from_millis(10)), Err(Error::PoolTimeout)));
+        assert!(matches!(pool.reader_timeout(Duration::from_millis(10)), Err(Error::PoolTimeout)));
+
+        checked.wait();
+    });
+
+    assert!(pool.try_writer().is_some());
+    assert!(pool.reader_timeout(Duration::from_millis(10)).is_ok());
+}
+
+#[test]
+fn reentering_fails_instead_of_waiting() {
+    let pool = pool("reentered", 2);
+
+    let writer = pool.writer().unwrap();
+    let first = pool.reader().unwrap();
+    let second = pool.reader().unwrap();
+
+    assert!(matches!(pool.writer(), Err(Error::PoolReentered)));
+    assert!(matches!(pool.writer_timeout(Duration::from_secs(60)), Err(Error::PoolReentered)));
+    assert!(matches!(pool.reader(), Err(Error::PoolReentered)));
+
+    drop(first);
+
+    assert!(pool.reader().is_ok());
+
+    drop(writer);
+    drop(second);
+
+    assert!(pool.writer().is_ok());
+}
+
+#[test]
+fn moved_handles_count_for_the_thread_using_them() {
+    let pool = pool("moved", 1);
+    let used = Barrier::new(2);
+
+    let writer = pool.writer().unwrap();
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            let mut writer = writer;
+
+            users(&mut writer, 1);
+            used.wait();
+
+            std::thread::sleep(Duration::from_millis(50));
+        });
+
+        used.wait();
+
+        // the writer is held by the other thread now, so this waits for it instead of failing
+        let mut writer = pool.writer().unwrap();
+
+        users(&mut writer, 1);
+        assert_eq!(writer.query::<User>().count().unwrap(), 2);
+    });
+}
+
+#[test]
+fn pools_need_a_reader() {
+    let pool = Pool::<Schema>::open(temp_path("empty"), 0, DatabaseConfig::new());
+
+    assert!(matches!(pool, Err(Error::OpenError(_))));
+}
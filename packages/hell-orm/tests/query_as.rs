@@ -0,0 +1,48 @@
+mod common;
+
+use common::users::{database, user_with_posts, Schema, User};
+
+use hell_orm::prelude::*;
+
+
+#[test]
+fn query_as_reads_columns_models_and_counts() {
+    let mut db = database();
+
+    let ada = user_with_posts(&mut db, "ada", 3);
+    user_with_posts(&mut db, "grace", 1);
+
+    let counts = query_as!(Schema, "SELECT users.name, COUNT(*) FROM posts INNER JOIN users ON users.id = posts.user WHERE posts.score >= ? GROUP BY users.id ORDER BY users.name", 0)
+        .all(&mut db)
+        .unwrap();
+
+    assert_eq!(counts, vec![(String::from("ada"), 3), (String::from("grace"), 1)]);
+
+    let user: Option<User> = query_as!(Schema, "SELECT * FROM users WHERE id = ?", ada).get(&mut db).unwrap();
+
+    assert_eq!(user.unwrap().name, "ada");
+}
+
+#[test]
+fn query_as_skips_aliases() {
+    let mut db = database();
+
+    user_with_posts(&mut db, "ada", 0);
+
+    let names = query_as!(Schema, "SELECT name n FROM users ORDER BY n").all(&mut db).unwrap();
+    let counts = query_as!(Schema, "SELECT COUNT(*) AS total FROM users WHERE name IN (?, ?)", "ada", "grace").all(&mut db).unwrap();
+
+    assert_eq!(names, vec![String::from("ada")]);
+    assert_eq!(counts, vec![1]);
+}
+
+#[test]
+fn query_as_reads_rowid() {
+    let mut db = database();
+
+    let ada = user_with_posts(&mut db, "ada", 0);
+
+    let rowids = query_as!(Schema, "SELECT rowid FROM users WHERE rowid = ?", ada as i64).all(&mut db).unwrap();
+
+    assert_eq!(rowids, vec![ada as i64]);
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use common::posts::database;
+
+
+#[test]
+fn execute_raw_returns_changed_rows() {
+    let mut db = database(3);
+
+    let changed = db.execute_raw("UPDATE posts SET score = score + 10 WHERE score > ?1", [0]).unwrap();
+
+    assert_eq!(changed, 2);
+    assert!(db.execute_raw("UPDATE missing SET x = 1", []).is_err());
+}
+
+#[test]
+fn query_raw_reads_tuples() {
+    let mut db = database(3);
+
+    let rows = db.query_raw::<(String, i64)>("SELECT content, score FROM posts WHERE score < ?1 ORDER BY score", [2]).unwrap();
+
+    assert_eq!(rows, vec![(String::from("post #0"), 0), (String::from("post #1"), 1)]);
+}
+
+#[test]
+fn with_connection_exposes_the_connection() {
+    let mut db = database(2);
+
+    let count = db.with_connection(|connection| connection.query_row("SELECT COUNT(*) FROM posts", [], |row| row.get::<_, i64>(0))).unwrap();
+
+    assert_eq!(count, 2);
+}
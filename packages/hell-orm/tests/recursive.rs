@@ -0,0 +1,171 @@
+use hell_orm::prelude::*;
+use hell_orm::schema::subquery::With;
+
+
+#[derive(Model, Debug)]
+#[table_name = "comments"]
+struct Comment {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    parent: Option<usize>,
+    body: String,
+}
+
+#[derive(Schema)]
+#[models(Comment)]
+struct Schema;
+
+/// Inserts `root -> a -> b` and a second root, returning the first root's id.
+fn thread(db: &mut Database<Schema>) -> usize {
+    let mut parent = None;
+
+    for body in ["root", "a", "b"] {
+        db.insert::<Comment>().parent(parent).body(String::from(body)).finish().unwrap();
+
+        parent = db.query::<Comment>().filter(Comment::BODY_COL.eq(body)).get().unwrap().map(|comment| comment.id);
+    }
+
+    db.insert::<Comment>().body(String::from("other")).finish().unwrap();
+
+    db.query::<Comment>().filter(Comment::BODY_COL.eq("root")).get().unwrap().unwrap().id
+}
+
+fn database() -> Database<Schema> {
+    Database::open_in_memory().unwrap()
+}
+
+/// Every comment other than the one with `body`, as the common table expression `replies`.
+fn others(db: &mut Database<Schema>, body: &str) -> With<usize> {
+    db.query::<Comment>()
+        .filter(Comment::BODY_COL.ne(body))
+        .subquery(Comment::ID_COL)
+        .named("replies")
+}
+
+fn bodies(comments: Vec<Comment>) -> Vec<String> {
+    comments.into_iter().map(|comment| comment.body).collect()
+}
+
+#[test]
+fn recursive_walks_the_tree_with_depths() {
+    let mut db = database();
+
+    let root = thread(&mut db);
+
+    let rows = db.query::<Comment>()
+        .filter(Comment::ID_COL.eq(root))
+        .recursive(on!(Comment::parent == Comment::id))
+        .all_with_depth()
+        .unwrap();
+
+    let rows = rows.into_iter().map(|(comment, depth)| (comment.body, depth)).collect::<Vec<(String, usize)>>();
+
+    assert_eq!(rows, vec![(String::from("root"), 0), (String::from("a"), 1), (String::from("b"), 2)]);
+}
+
+#[test]
+fn max_depth_stops_the_walk() {
+    let mut db = database();
+
+    let root = thread(&mut db);
+
+    let rows = db.query::<Comment>()
+        .filter(Comment::ID_COL.eq(root))
+        .recursive(on!(Comment::parent == Comment::id))
+        .max_depth(1)
+        .all()
+        .unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1].parent, Some(root));
+}
+
+#[test]
+fn with_defines_a_plain_common_table_expression() {
+    let mut db = database();
+
+    thread(&mut db);
+
+    let roots = db.query::<Comment>()
+        .filter(Comment::PARENT_COL.is_null())
+        .filter(Comment::BODY_COL.ne("other"))
+        .subquery(Comment::ID_COL)
+        .named("roots");
+
+    let found = db.query::<Comment>()
+        .filter(Comment::BODY_COL.ne("a"))
+        .filter(Comment::ID_COL.in_(roots.table()))
+        .with(roots)
+        .all()
+        .unwrap();
+
+    assert_eq!(bodies(found), vec!["root"]);
+}
+
+#[test]
+fn with_numbers_its_parameters_after_the_filters() {
+    let mut db = database();
+
+    thread(&mut db);
+
+    // both the filter and the expression bind a parameter, in every kind of statement
+    for (expected, body) in [(2, "root"), (3, "b")] {
+        let replies = others(&mut db, body);
+
+        let count = db.query::<Comment>()
+            .filter(Comment::BODY_COL.ne("b"))
+            .filter(Comment::ID_COL.in_(replies.table()))
+            .with(replies)
+            .count()
+            .unwrap();
+
+        assert_eq!(count, expected);
+    }
+
+    let replies = others(&mut db, "root");
+
+    let last = db.query::<Comment>()
+        .filter(Comment::BODY_COL.ne("b"))
+        .filter(Comment::ID_COL.in_(replies.table()))
+        .with(replies)
+        .limit(1)
+        .last()
+        .unwrap();
+
+    assert_eq!(last.map(|comment| comment.body), Some(String::from("a")));
+
+    let replies = others(&mut db, "root");
+
+    let deleted = db.query::<Comment>()
+        .filter(Comment::BODY_COL.ne("b"))
+        .filter(Comment::ID_COL.in_(replies.table()))
+        .with(replies)
+        .delete()
+        .unwrap();
+
+    assert_eq!(deleted, 2);
+    assert_eq!(bodies(db.query::<Comment>().all().unwrap()), vec!["root", "b"]);
+}
+
+#[test]
+fn with_tables_can_start_a_recursive_walk() {
+    let mut db = database();
+
+    thread(&mut db);
+
+    let start = db.query::<Comment>()
+        .filter(Comment::BODY_COL.eq("a"))
+        .subquery(Comment::ID_COL)
+        .named("start");
+
+    let rows = db.query::<Comment>()
+        .filter(Comment::ID_COL.in_(start.table()))
+        .with(start)
+        .recursive(on!(Comment::parent == Comment::id))
+        .all()
+        .unwrap();
+
+    assert_eq!(bodies(rows), vec!["a", "b"]);
+}
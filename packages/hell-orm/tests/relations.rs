@@ -0,0 +1,125 @@
+use hell_orm::prelude::*;
+
+
+#[derive(Model, Debug)]
+#[table_name = "users"]
+struct User {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    name: String,
+}
+
+#[derive(Model, Debug)]
+#[table_name = "posts"]
+#[soft_delete]
+#[belongs_to(User, key = "user")]
+struct Post {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    user: usize,
+    score: i64,
+}
+
+#[derive(Model, Debug)]
+#[table_name = "reviews"]
+#[belongs_to(User, key = "reviewer", as = "reviewed")]
+struct Review {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    reviewer: usize,
+}
+
+#[derive(Model, Debug)]
+#[table_name = "comments"]
+#[belongs_to(Comment, key = "parent", as = "replies")]
+struct Comment {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    parent: Option<usize>,
+    body: String,
+}
+
+#[derive(Schema)]
+#[models(User, Post, Review, Comment)]
+struct Schema;
+
+fn user(db: &mut Database<Schema>, name: &str) -> User {
+    db.insert::<User>().name(String::from(name)).finish().unwrap();
+
+    db.query::<User>().name(Where::Equal(String::from(name))).get().unwrap().unwrap()
+}
+
+#[test]
+fn belongs_to_navigates_both_ways() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    let ada = user(&mut db, "ada");
+    let grace = user(&mut db, "grace");
+
+    for (author, score) in [(ada.id, 0), (ada.id, 1), (grace.id, 2)] {
+        db.insert::<Post>().user(author).score(score).finish().unwrap();
+    }
+
+    let posts = ada.posts(&mut db).unwrap();
+
+    assert_eq!(posts.len(), 2);
+    assert!(posts.iter().all(|post| post.user == ada.id));
+    assert_eq!(posts[0].user(&mut db).unwrap().unwrap().name, "ada");
+}
+
+#[test]
+fn children_skip_soft_deleted_rows() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    let ada = user(&mut db, "ada");
+
+    for score in 0..2 {
+        db.insert::<Post>().user(ada.id).score(score).finish().unwrap();
+    }
+
+    db.query::<Post>().score(Where::Equal(0)).delete().unwrap();
+
+    let posts = ada.posts(&mut db).unwrap();
+
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].score, 1);
+}
+
+#[test]
+fn children_methods_can_be_named() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    let ada = user(&mut db, "ada");
+
+    db.insert::<Review>().reviewer(ada.id).finish().unwrap();
+
+    let reviews = ada.reviewed(&mut db).unwrap();
+
+    assert_eq!(reviews.len(), 1);
+    assert_eq!(reviews[0].reviewer(&mut db).unwrap().unwrap().id, ada.id);
+}
+
+#[test]
+fn optional_keys_navigate_trees() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<Comment>().body(String::from("root")).finish().unwrap();
+
+    let root = db.query::<Comment>().get().unwrap().unwrap();
+
+    db.insert::<Comment>().parent(Some(root.id)).body(String::from("reply")).finish().unwrap();
+
+    let replies = root.replies(&mut db).unwrap();
+
+    assert_eq!(replies.iter().map(|reply| reply.body.as_str()).collect::<Vec<_>>(), vec!["reply"]);
+    assert_eq!(replies[0].parent(&mut db).unwrap().map(|parent| parent.id), Some(root.id));
+    assert!(root.parent(&mut db).unwrap().is_none());
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use common::posts::{database, Post};
+
+use hell_orm::prelude::*;
+
+
+#[derive(Projection)]
+#[projection(Post)]
+struct Summary {
+    id: usize,
+    score: i64,
+}
+
+#[test]
+fn select_reads_single_columns_and_tuples() {
+    let mut db = database(3);
+
+    let scores = db.query::<Post>().order_by(Post::SCORE_COL.desc()).select(Post::SCORE_COL).unwrap();
+
+    assert_eq!(scores, vec![2, 1, 0]);
+
+    let posts = db.query::<Post>().filter(Post::SCORE_COL.lt(2)).select((Post::ID_COL, Post::CONTENT_COL)).unwrap();
+
+    assert_eq!(posts, vec![(1, String::from("post #0")), (2, String::from("post #1"))]);
+}
+
+#[test]
+fn select_as_fills_projections() {
+    let mut db = database(2);
+
+    let summaries = db.query::<Post>().filter(Post::SCORE_COL.eq(1)).select_as::<Summary>().unwrap();
+
+    assert_eq!(summaries.len(), 1);
+    assert_eq!((summaries[0].id, summaries[0].score), (2, 1));
+}
@@ -0,0 +1,88 @@
+use hell_orm::prelude::*;
+
+
+#[derive(Model, Debug)]
+#[table_name = "posts"]
+#[soft_delete]
+struct Post {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    content: String,
+    score: i64,
+}
+
+#[derive(Model, Debug)]
+#[table_name = "notes"]
+struct Note {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    body: String,
+}
+
+#[derive(Schema)]
+#[models(Post, Note)]
+struct Schema;
+
+fn posts(db: &mut Database<Schema>, count: i64) {
+    for score in 0..count {
+        db.insert::<Post>().content(format!("post #{}", score)).score(score).finish().unwrap();
+    }
+}
+
+#[test]
+fn delete_hides_rows_instead_of_removing_them() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    posts(&mut db, 3);
+
+    assert_eq!(db.query::<Post>().score(Where::Equal(0)).delete().unwrap(), 1);
+
+    assert_eq!(db.query::<Post>().all().unwrap().len(), 2);
+    assert_eq!(db.query::<Post>().with_deleted().all().unwrap().len(), 3);
+    assert_eq!(db.query::<Post>().only_deleted().get().unwrap().unwrap().score, 0);
+}
+
+#[test]
+fn deleted_rows_are_excluded_from_updates() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    posts(&mut db, 2);
+
+    db.query::<Post>().score(Where::Equal(1)).delete().unwrap();
+
+    assert_eq!(db.query::<Post>().update().content(String::from("edited")).finish().unwrap(), 1);
+    assert_eq!(db.query::<Post>().only_deleted().get().unwrap().unwrap().content, "post #1");
+}
+
+#[test]
+fn delete_honours_order_limit_and_offset() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    posts(&mut db, 5);
+
+    assert_eq!(db.query::<Post>().order_by(Post::SCORE_COL.desc()).limit(2).offset(1).delete().unwrap(), 2);
+
+    let scores = db.query::<Post>().order_by(Post::SCORE_COL.asc()).all().unwrap().into_iter().map(|post| post.score).collect::<Vec<i64>>();
+    let deleted = db.query::<Post>().only_deleted().order_by(Post::SCORE_COL.asc()).all().unwrap().into_iter().map(|post| post.score).collect::<Vec<i64>>();
+
+    assert_eq!((scores, deleted), (vec![0, 1, 4], vec![2, 3]));
+}
+
+#[test]
+fn models_without_soft_delete_are_removed() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<Note>().body(String::from("draft")).finish().unwrap();
+    db.insert::<Note>().body(String::from("final")).finish().unwrap();
+
+    assert_eq!(db.query::<Note>().body(Where::Equal(String::from("draft"))).delete().unwrap(), 1);
+
+    let notes = db.query::<Note>().all().unwrap();
+
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].body, "final");
+}
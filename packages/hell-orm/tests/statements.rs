@@ -0,0 +1,28 @@
+mod common;
+
+use common::posts::{database, Post};
+
+
+#[test]
+fn in_lists_of_every_length_share_a_small_cache() {
+    let mut db = database(20);
+
+    db.set_statement_cache_capacity(2);
+
+    for length in 0..20 {
+        let scores = (0..length).collect::<Vec<i64>>();
+
+        let posts = db.query::<Post>().filter(Post::SCORE_COL.in_(scores.clone())).order_by(Post::SCORE_COL.asc()).all().unwrap();
+        assert_eq!(posts.into_iter().map(|post| post.score).collect::<Vec<i64>>(), scores);
+
+        assert_eq!(db.query::<Post>().filter(Post::SCORE_COL.in_((0..length).collect::<Vec<i64>>())).count().unwrap(), length);
+        assert_eq!(db.query::<Post>().filter(Post::SCORE_COL.ge(length)).count().unwrap(), 20 - length);
+    }
+
+    let updated = db.query::<Post>().filter(Post::SCORE_COL.in_([1, 2, 3])).update().content(String::from("edited")).finish().unwrap();
+    assert_eq!(updated, 3);
+    assert_eq!(db.query::<Post>().filter(Post::SCORE_COL.eq(2)).get().unwrap().unwrap().content, "edited");
+
+    assert_eq!(db.query::<Post>().filter(Post::SCORE_COL.in_([1, 2])).delete().unwrap(), 2);
+    assert_eq!(db.query::<Post>().count().unwrap(), 18);
+}
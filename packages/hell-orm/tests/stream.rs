@@ -0,0 +1,33 @@
+mod common;
+
+use common::posts::{database, Post};
+
+use hell_orm::error::Error;
+
+
+#[test]
+fn for_each_visits_rows_in_order() {
+    let mut db = database(4);
+
+    let mut scores = Vec::new();
+
+    db.query::<Post>().filter(Post::SCORE_COL.ne(1)).order_by(Post::SCORE_COL.desc()).for_each(|post| scores.push((post.id, post.score))).unwrap();
+
+    assert_eq!(scores, vec![(4, 3), (3, 2), (1, 0)]);
+}
+
+#[test]
+fn try_for_each_stops_at_the_first_error() {
+    let mut db = database(4);
+
+    let mut seen = 0;
+
+    let result = db.query::<Post>().order_by(Post::SCORE_COL.asc()).try_for_each(|post| {
+        seen += 1;
+
+        if post.score == 1 { Err(Error::StaleVersion) } else { Ok(()) }
+    });
+
+    assert!(matches!(result, Err(Error::StaleVersion)));
+    assert_eq!(seen, 2);
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use common::users::{database, user_with_posts, Post, User};
+
+use hell_orm::prelude::*;
+
+
+#[test]
+fn in_subquery_filters_by_selected_values() {
+    let mut db = database();
+
+    user_with_posts(&mut db, "ada", 3);
+    user_with_posts(&mut db, "grace", 1);
+    user_with_posts(&mut db, "linus", 4);
+
+    let prolific = db.query::<Post>().filter(Post::SCORE_COL.ge(2)).subquery(Post::USER_COL);
+
+    let names = db.query::<User>()
+        .filter(User::NAME_COL.ne("linus"))
+        .filter(User::ID_COL.in_(prolific))
+        .select(User::NAME_COL)
+        .unwrap();
+
+    assert_eq!(names, vec![String::from("ada")]);
+}
+
+#[test]
+fn exists_subquery_matches_when_rows_are_found() {
+    let mut db = database();
+
+    user_with_posts(&mut db, "ada", 1);
+
+    let high = db.query::<Post>().filter(Post::SCORE_COL.gt(5)).subquery(Post::ID_COL);
+    let any = db.query::<Post>().filter(Post::SCORE_COL.ge(0)).subquery(Post::ID_COL);
+
+    assert_eq!(db.query::<User>().filter(exists(high)).count().unwrap(), 0);
+    assert_eq!(db.query::<User>().filter(exists(any).and(User::NAME_COL.eq("ada"))).count().unwrap(), 1);
+}
+
+#[test]
+fn subqueries_skip_soft_deleted_rows() {
+    let mut db = database();
+
+    user_with_posts(&mut db, "ada", 1);
+
+    db.query::<Post>().delete().unwrap();
+
+    let authors = db.query::<Post>().subquery(Post::USER_COL);
+
+    assert_eq!(db.query::<User>().filter(User::ID_COL.in_(authors)).count().unwrap(), 0);
+}
@@ -0,0 +1,105 @@
+use hell_orm::prelude::*;
+
+
+#[derive(Model, Debug)]
+#[table_name = "posts"]
+struct Post {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    user: usize,
+    score: i64,
+}
+
+#[derive(Schema)]
+#[models(Post)]
+struct Schema;
+
+/// A database with one post per `(user, score)` pair, inserted in order.
+fn database(posts: &[(usize, i64)]) -> Database<Schema> {
+    let mut db = Database::open_in_memory().unwrap();
+
+    for &(user, score) in posts {
+        db.insert::<Post>().user(user).score(score).finish().unwrap();
+    }
+
+    db
+}
+
+fn score(post: Option<Post>) -> Option<i64> {
+    post.map(|post| post.score)
+}
+
+#[test]
+fn first_and_last_default_to_primary_key_order() {
+    let mut db = database(&[(1, 0), (1, 1), (1, 2)]);
+
+    assert_eq!(score(db.query::<Post>().first().unwrap()), Some(0));
+    assert_eq!(score(db.query::<Post>().last().unwrap()), Some(2));
+}
+
+#[test]
+fn first_and_last_follow_order_by() {
+    let mut db = database(&[(1, 0), (1, 1), (2, 2), (2, 3)]);
+
+    let first = db.query::<Post>().order_by(Post::USER_COL.desc()).first().unwrap().unwrap();
+    let last = db.query::<Post>().order_by(Post::USER_COL.desc()).last().unwrap().unwrap();
+
+    assert_eq!((first.user, first.score), (2, 2));
+    assert_eq!((last.user, last.score), (1, 1));
+}
+
+#[test]
+fn last_honours_limit_and_offset() {
+    let mut db = database(&[(1, 0), (1, 1), (1, 2), (1, 3), (1, 4)]);
+
+    assert_eq!(score(db.query::<Post>().offset(2).last().unwrap()), Some(4));
+    assert_eq!(score(db.query::<Post>().limit(2).last().unwrap()), Some(1));
+    assert_eq!(score(db.query::<Post>().limit(2).offset(1).last().unwrap()), Some(2));
+    assert_eq!(score(db.query::<Post>().order_by(Post::SCORE_COL.desc()).limit(3).last().unwrap()), Some(2));
+    assert_eq!(score(db.query::<Post>().offset(5).last().unwrap()), None);
+}
+
+#[test]
+fn get_keeps_offset_and_an_empty_limit() {
+    let mut db = database(&[(1, 0), (1, 1), (1, 2)]);
+
+    assert_eq!(score(db.query::<Post>().offset(1).get().unwrap()), Some(1));
+    assert_eq!(score(db.query::<Post>().limit(5).get().unwrap()), Some(0));
+    assert_eq!(score(db.query::<Post>().limit(0).get().unwrap()), None);
+}
+
+#[test]
+fn exists_checks_for_matches() {
+    let mut db = database(&[(1, 0)]);
+
+    assert!(db.query::<Post>().exists().unwrap());
+    assert!(!db.query::<Post>().filter(Post::USER_COL.eq(2)).exists().unwrap());
+}
+
+#[test]
+fn exists_honours_offset() {
+    let mut db = database(&[(1, 0), (1, 1)]);
+
+    assert!(db.query::<Post>().offset(1).exists().unwrap());
+    assert!(!db.query::<Post>().offset(2).exists().unwrap());
+}
+
+#[test]
+fn distinct_removes_duplicate_selections() {
+    let mut db = database(&[(1, 0), (1, 1), (1, 2), (2, 0), (2, 1)]);
+
+    assert_eq!(db.query::<Post>().select(Post::USER_COL).unwrap().len(), 5);
+    assert_eq!(db.query::<Post>().distinct().select(Post::USER_COL).unwrap().len(), 2);
+}
+
+#[test]
+fn get_by_id_finds_one_row() {
+    let mut db = database(&[(1, 7)]);
+
+    let id = db.query::<Post>().first().unwrap().unwrap().id;
+
+    assert_eq!(score(db.query::<Post>().get_by_id(id).unwrap()), Some(7));
+    assert!(db.query::<Post>().get_by_id(id + 1).unwrap().is_none());
+}
@@ -0,0 +1,91 @@
+use hell_orm::prelude::*;
+
+
+#[derive(Model, Debug)]
+#[table_name = "posts"]
+#[timestamps]
+struct Post {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    content: String,
+
+    created_at: i64,
+    updated_at: i64,
+}
+
+#[derive(Model, Debug)]
+#[table_name = "events"]
+struct Event {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    name: String,
+
+    #[created_at]
+    opened: u64,
+
+    #[updated_at]
+    changed: u64,
+}
+
+#[derive(Schema)]
+#[models(Post, Event)]
+struct Schema;
+
+#[test]
+fn insert_fills_timestamps() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<Post>().content(String::from("new")).finish().unwrap();
+
+    let post = db.query::<Post>().get().unwrap().unwrap();
+
+    assert!(post.created_at > 0);
+    assert_eq!(post.created_at, post.updated_at);
+}
+
+#[test]
+fn explicit_timestamps_are_kept() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<Post>().content(String::from("old")).created_at(1).updated_at(2).finish().unwrap();
+
+    let post = db.query::<Post>().get().unwrap().unwrap();
+
+    assert_eq!((post.created_at, post.updated_at), (1, 2));
+}
+
+#[test]
+fn update_refreshes_updated_at() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<Post>().content(String::from("old")).created_at(1).updated_at(1).finish().unwrap();
+    db.query::<Post>().update().content(String::from("new")).finish().unwrap();
+
+    let post = db.query::<Post>().get().unwrap().unwrap();
+
+    assert_eq!(post.content, "new");
+    assert_eq!(post.created_at, 1);
+    assert!(post.updated_at > 1);
+}
+
+#[test]
+fn field_attributes_name_their_own_columns() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<Event>().name(String::from("launch")).finish().unwrap();
+
+    let event = db.query::<Event>().get().unwrap().unwrap();
+
+    assert!(event.opened > 0);
+    assert_eq!(event.opened, event.changed);
+
+    db.query::<Event>().update().name(String::from("relaunch")).changed(1).finish().unwrap();
+
+    let event = db.query::<Event>().get().unwrap().unwrap();
+
+    assert_eq!((event.name.as_str(), event.changed), ("relaunch", 1));
+}
@@ -0,0 +1,67 @@
+use hell_orm::error::Error;
+use hell_orm::prelude::*;
+
+
+#[derive(Model, Debug)]
+#[table_name = "users"]
+struct User {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    name: String,
+}
+
+#[derive(Schema)]
+#[models(User)]
+struct Schema;
+
+fn database() -> Database<Schema> {
+    Database::open_in_memory().unwrap()
+}
+
+/// Inserts a user named `name`, returning its id.
+fn user(db: &mut Database<Schema>, name: &str) -> usize {
+    db.insert::<User>().name(String::from(name)).finish().unwrap();
+
+    db.query::<User>().name(Where::Equal(String::from(name))).get().unwrap().unwrap().id
+}
+
+
+#[test]
+fn transactions_commit_on_ok() {
+    let mut db = database();
+
+    let id = db.transaction(|db| Ok(user(db, "ada"))).unwrap();
+
+    assert_eq!(db.query::<User>().get_by_id(id).unwrap().map(|user| user.name), Some(String::from("ada")));
+}
+
+#[test]
+fn transactions_roll_back_on_err() {
+    let mut db = database();
+
+    let result = db.transaction(|db| {
+        user(db, "ada");
+
+        Err::<(), _>(Error::StaleVersion)
+    });
+
+    assert!(matches!(result, Err(Error::StaleVersion)));
+    assert_eq!(db.query::<User>().count().unwrap(), 0);
+}
+
+#[test]
+fn transactions_keep_the_callers_error() {
+    let mut db = database();
+
+    // `f` ends the transaction itself, so there is nothing left to roll back
+    let result = db.transaction(|db| {
+        db.execute_raw("COMMIT", [])?;
+
+        Err::<(), _>(Error::StaleVersion)
+    });
+
+    assert!(matches!(result, Err(Error::StaleVersion)));
+    assert!(db.transaction(|_| Ok(())).is_ok());
+}
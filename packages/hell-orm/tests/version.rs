@@ -0,0 +1,62 @@
+use hell_orm::prelude::*;
+use hell_orm::error::Error;
+
+
+#[derive(Model, Debug)]
+#[table_name = "users"]
+struct User {
+    #[primary_key]
+    #[auto_increment]
+    id: usize,
+
+    name: String,
+
+    #[version]
+    version: u32,
+}
+
+#[derive(Schema)]
+#[models(User)]
+struct Schema;
+
+#[test]
+fn update_bumps_the_version() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<User>().name(String::from("ada")).finish().unwrap();
+
+    db.query::<User>().update().name(String::from("ada lovelace")).finish().unwrap();
+
+    let user = db.query::<User>().get().unwrap().unwrap();
+
+    assert_eq!(user.name, "ada lovelace");
+    assert_eq!(user.version, 1);
+}
+
+#[test]
+fn stale_expected_version_is_rejected() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<User>().name(String::from("ada")).finish().unwrap();
+
+    let user = db.query::<User>().get().unwrap().unwrap();
+
+    db.query::<User>().id(Where::Equal(user.id)).update().name(String::from("first")).expect_version(user.version).finish().unwrap();
+
+    let stale = db.query::<User>().id(Where::Equal(user.id)).update().name(String::from("second")).expect_version(user.version).finish();
+
+    assert!(matches!(stale, Err(Error::StaleVersion)));
+    assert_eq!(db.query::<User>().get().unwrap().unwrap().name, "first");
+}
+
+#[test]
+fn stale_version_is_reported_without_changed_columns() {
+    let mut db = Database::<Schema>::open_in_memory().unwrap();
+
+    db.insert::<User>().name(String::from("ada")).finish().unwrap();
+
+    let user = db.query::<User>().get().unwrap().unwrap();
+
+    assert_eq!(db.query::<User>().update().expect_version(user.version).finish().unwrap(), 0);
+    assert!(matches!(db.query::<User>().update().expect_version(user.version + 1).finish(), Err(Error::StaleVersion)));
+}